@@ -1,29 +1,703 @@
-﻿use clap::{Command, ArgMatches};
-use crate::go::cli::{go_subcommand, handle_go_commands};
-use crate::java::cli::{handle_java_commands, java_subcommand};
-use crate::nodejs::cli::{handle_node_commands, node_subcommand};
-use crate::python::cli::{handle_python_commands, python_subcommand};
-use crate::rust::cli::{rust_subcommand, handle_rust_commands};
+use std::ffi::OsString;
+use std::path::PathBuf;
+use clap::{Arg, ArgMatches, Command};
+use language_revamp::config::{self, Config, Entry, Source};
+use language_revamp::error::{Error, Outcome, Result};
+use language_revamp::manager::{find_manager, registry, LanguageManager};
+use language_revamp::output::{confirm, emit, is_structured, say, OutputFormat};
+use language_revamp::report::{Action, Report, Status, Verdict};
+use language_revamp::settings::{self, Settings};
+use language_revamp::utils::cache::{format_age, oldest_used};
+use language_revamp::utils::command::{is_dry_run, plan};
+use language_revamp::utils::paths::config_file;
+use language_revamp::utils::shims::{print_path_hint, run_shim};
+use language_revamp::utils::version::Version;
 
 pub fn build_cli() -> Command {
     Command::new("language-revamp")
         .version("1.1.0")
         .author("Ddokubi")
         .about("A CLI tool to manage programming languages")
-        .subcommand(go_subcommand())
-        .subcommand(rust_subcommand())
-        .subcommand(python_subcommand())
-        .subcommand(node_subcommand())
-        .subcommand(java_subcommand())
+        .arg(
+            Arg::new("root")
+                .long("root")
+                .value_name("DIR")
+                .global(true)
+                .value_parser(clap::value_parser!(PathBuf))
+                .help("Directory where managed toolchains are installed (default: per-user data dir)"),
+        )
+        .arg(
+            Arg::new("system")
+                .long("system")
+                .global(true)
+                .conflicts_with("root")
+                .help("Install system-wide into /usr/local using sudo instead of the per-user root")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("dry-run")
+                .long("dry-run")
+                .global(true)
+                .help("Print what install, update and uninstall would do without changing anything")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("offline")
+                .long("offline")
+                .global(true)
+                .help("Answer check, status, outdated and list-remote from cached release metadata only")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("refresh")
+                .long("refresh")
+                .global(true)
+                .conflicts_with("offline")
+                .help("Fetch release metadata again even if the cached copy is still fresh")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .value_name("FORMAT")
+                .global(true)
+                .value_parser(["text", "json", "yaml"])
+                .default_value("text")
+                .help("Output format for check, install and update results"),
+        )
+        .subcommands(registry().iter().map(|manager| language_subcommand(manager.as_ref())))
+        .subcommand(
+            Command::new("status")
+                .about("Show the installed and latest version of every language")
+                .arg(
+                    Arg::new("no-latest")
+                        .long("no-latest")
+                        .help("Skip looking up the latest available versions")
+                        .action(clap::ArgAction::SetTrue),
+                ),
+        )
+        .subcommand(
+            Command::new("outdated")
+                .about("List languages whose installed version is behind the latest release"),
+        )
+        .subcommand(
+            Command::new("upgrade")
+                .about("Update outdated languages to their latest release")
+                .arg(
+                    Arg::new("languages")
+                        .value_name("LANGUAGE")
+                        .num_args(1..)
+                        .value_parser(registry().iter().map(|manager| manager.name()).collect::<Vec<_>>())
+                        .help("Languages to upgrade"),
+                )
+                .arg(
+                    Arg::new("all")
+                        .long("all")
+                        .conflicts_with("languages")
+                        .help("Upgrade every outdated language")
+                        .action(clap::ArgAction::SetTrue),
+                )
+                .arg(
+                    Arg::new("yes")
+                        .short('y')
+                        .long("yes")
+                        .help("Do not ask for confirmation")
+                        .action(clap::ArgAction::SetTrue),
+                ),
+        )
+        .subcommand(
+            Command::new("config")
+                .about("Show or change settings in the configuration file")
+                .subcommand_required(true)
+                .subcommand(Command::new("list").about("Show every setting with its value and where it comes from"))
+                .subcommand(
+                    Command::new("get")
+                        .about("Print the effective value of a setting")
+                        .arg(Arg::new("key").required(true)),
+                )
+                .subcommand(
+                    Command::new("set")
+                        .about("Store a setting in the configuration file")
+                        .arg(Arg::new("key").required(true))
+                        .arg(Arg::new("value").required(true)),
+                )
+                .subcommand(
+                    Command::new("unset")
+                        .about("Remove a setting from the configuration file")
+                        .arg(Arg::new("key").required(true)),
+                ),
+        )
+        .subcommand(
+            Command::new("reshim")
+                .about("Regenerate the shims for every managed toolchain"),
+        )
 }
 
-pub async fn handle_cli(matches: ArgMatches) {
+fn language_subcommand(manager: &dyn LanguageManager) -> Command {
+    let name = manager.display_name();
+
+    Command::new(manager.name())
+        .about(format!("Manage {} installation", name))
+        .arg(
+            Arg::new("check")
+                .short('c')
+                .long("check")
+                .help(format!("Check the installed {} version", name))
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("update")
+                .short('u')
+                .long("update")
+                .visible_alias("update-manual")
+                .help(format!("Update {} to the latest version", name))
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("install")
+                .short('i')
+                .long("install")
+                .short_alias('d')
+                .visible_alias("download")
+                .value_name("VERSION")
+                .num_args(0..=1)
+                .help(format!("Install {} (the latest version unless VERSION is given)", name)),
+        )
+        .arg(
+            Arg::new("uninstall")
+                .long("uninstall")
+                .value_name("VERSION")
+                .num_args(0..=1)
+                .help(format!("Uninstall {} (only VERSION if given)", name)),
+        )
+        .arg(
+            Arg::new("yes")
+                .short('y')
+                .long("yes")
+                .help("Do not ask for confirmation")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .args(manager.extra_args())
+        .subcommand(
+            Command::new("list")
+                .about(format!("List installed {} versions", name)),
+        )
+        .subcommand(
+            Command::new("list-remote")
+                .about(format!("List {} releases available for installation", name)),
+        )
+        .subcommand(
+            Command::new("use")
+                .about(format!("Switch the default {} version", name))
+                .arg(
+                    Arg::new("version")
+                        .required(true)
+                        .help("Installed version to use, e.g. 1.21 or 1.21.13"),
+                ),
+        )
+}
+
+pub async fn handle_cli(matches: ArgMatches) -> Result<Outcome> {
+    let mut config = Config::load()?;
+    if let Some(root) = matches.get_one::<PathBuf>("root") {
+        config.override_with("root", root.display().to_string());
+    }
+    if matches.get_flag("system") {
+        config.override_with("system", "true");
+    }
+    if let Some((name, sub_matches)) = matches.subcommand()
+        && let Some(manager) = find_manager(name)
+    {
+        manager.configure(sub_matches, &mut config)?;
+    }
+    settings::init(Settings {
+        format: matches
+            .get_one::<String>("format")
+            .and_then(|format| OutputFormat::parse(format))
+            .unwrap_or_default(),
+        dry_run: matches.get_flag("dry-run"),
+        offline: matches.get_flag("offline"),
+        refresh: matches.get_flag("refresh"),
+        ..Settings::from_config(config)
+    })?;
+    for warning in settings::get().config.warnings() {
+        eprintln!("Warning: {}", warning);
+    }
+
+    let result = match matches.subcommand() {
+        Some(("status", status_matches)) => show_status(!status_matches.get_flag("no-latest")).await,
+        Some(("outdated", _)) => list_outdated().await,
+        Some(("upgrade", upgrade_matches)) => upgrade(upgrade_matches).await,
+        Some(("config", config_matches)) => handle_config(config_matches),
+        Some(("reshim", _)) => reshim(),
+        Some((name, sub_matches)) => match find_manager(name) {
+            Some(manager) => handle_language_commands(manager.as_ref(), sub_matches).await,
+            None => Err(usage_error()),
+        },
+        _ => Err(usage_error()),
+    };
+
+    if is_dry_run() {
+        plan("no changes were made");
+    }
+    if settings::get().offline
+        && let Some(age) = oldest_used()
+    {
+        say!("(offline: release metadata from the cache, up to {} old)", format_age(age));
+    }
+    result
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ShimCall {
+    root: Option<PathBuf>,
+    binary: String,
+    args: Vec<OsString>,
+}
+
+pub fn shim_call(args: &[OsString]) -> Option<ShimCall> {
+    let mut rest = args.get(1..)?;
+    let mut root = None;
+    if rest.first()? == "--root" {
+        root = Some(PathBuf::from(rest.get(1)?));
+        rest = &rest[2..];
+    }
+    if rest.first()? != "exec" {
+        return None;
+    }
+
+    Some(ShimCall {
+        root,
+        binary: rest.get(1)?.to_str()?.to_string(),
+        args: rest[2..].to_vec(),
+    })
+}
+
+pub fn handle_shim_call(call: ShimCall) -> Result<Outcome> {
+    let mut config = Config::load()?;
+    if let Some(root) = &call.root {
+        config.override_with("root", root.display().to_string());
+    }
+    settings::init(Settings::from_config(config))?;
+
+    run_shim(&call.binary, &call.args)?;
+    Ok(Outcome::Success)
+}
+
+async fn show_status(include_latest: bool) -> Result<Outcome> {
+    let reports = language_revamp::status(include_latest).await?;
+
+    if is_structured() {
+        emit(&reports)?;
+    } else {
+        print_status_table(&reports);
+    }
+
+    if reports.iter().any(|report| report.status == Status::Outdated) {
+        Ok(Outcome::Outdated)
+    } else {
+        Ok(Outcome::Success)
+    }
+}
+
+fn print_status_table(reports: &[Report]) {
+    let rows: Vec<[String; 5]> = reports
+        .iter()
+        .map(|report| {
+            let marker = match report.status {
+                Status::UpToDate => "✔",
+                Status::Outdated => "✘",
+                _ => "-",
+            };
+            [
+                report.language.clone(),
+                report.installed.clone().unwrap_or_else(|| "not installed".to_string()),
+                report.path.as_ref().map(|path| path.display().to_string()).unwrap_or_default(),
+                report.latest.clone().unwrap_or_else(|| "?".to_string()),
+                marker.to_string(),
+            ]
+        })
+        .collect();
+
+    let headers = ["LANGUAGE", "INSTALLED", "PATH", "LATEST", "UP TO DATE"];
+    let mut widths = headers.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    for row in std::iter::once(headers.map(str::to_string)).chain(rows) {
+        let line = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ");
+        say!("{}", line.trim_end());
+    }
+}
+
+async fn list_outdated() -> Result<Outcome> {
+    let outdated = language_revamp::outdated().await?;
+
+    if is_structured() {
+        emit(&outdated)?;
+    } else if outdated.is_empty() {
+        say!("Everything is up to date.");
+    } else {
+        for report in &outdated {
+            say!("{}", describe_upgrade(report));
+        }
+    }
+
+    if outdated.is_empty() {
+        Ok(Outcome::Success)
+    } else {
+        Ok(Outcome::Outdated)
+    }
+}
+
+async fn upgrade(matches: &ArgMatches) -> Result<Outcome> {
+    if settings::get().offline {
+        return Err(offline_error());
+    }
+    let selected: Vec<&String> = matches.get_many::<String>("languages").map(Iterator::collect).unwrap_or_default();
+    if selected.is_empty() && !matches.get_flag("all") {
+        return Err(Error::Usage(
+            "Name the languages to upgrade or pass --all. Run 'language-revamp upgrade --help' for details.".to_string(),
+        ));
+    }
+
+    let (pinned, plan): (Vec<Report>, Vec<Report>) = language_revamp::outdated()
+        .await?
+        .into_iter()
+        .filter(|report| selected.is_empty() || selected.contains(&&report.language))
+        .partition(is_pinned);
+    for report in &pinned {
+        say!(
+            "Skipping {}: the project requests {}; run 'language-revamp {} --update' to follow it.",
+            report.language,
+            report.requested.as_deref().unwrap_or("?"),
+            report.language
+        );
+    }
+    if plan.is_empty() {
+        say!("Nothing to upgrade.");
+        return Ok(Outcome::Success);
+    }
+
+    say!("The following languages will be upgraded:");
+    for report in &plan {
+        say!("  {}", describe_upgrade(report));
+    }
+    if !assume_yes(matches) && !is_dry_run() && !confirm("Proceed?")? {
+        return Err(Error::Other("Upgrade cancelled".to_string()));
+    }
+
+    let mut results = Vec::new();
+    for report in &plan {
+        let manager = find_manager(&report.language).expect("report language is registered");
+        say!("\nUpgrading {}...", manager.display_name());
+        let report = match language_revamp::update(manager.name()).await {
+            Ok(report) => report,
+            Err(e) => Report::collect(manager.as_ref(), Action::Update, Some(&e), false).await,
+        };
+        results.push(report);
+    }
+
+    if is_structured() {
+        emit(&results)?;
+    } else {
+        say!("\nSummary:");
+        for report in &results {
+            if report.status == Status::Failed {
+                say!("  ✘ {}: {}", report.language, report.errors.join("; "));
+            } else {
+                say!("  ✔ {} {}", report.language, report.installed.as_deref().unwrap_or("?"));
+            }
+        }
+    }
+
+    let failed = results.iter().filter(|report| report.status == Status::Failed).count();
+    if failed > 0 {
+        return Err(Error::Other(format!("{} of {} upgrades failed", failed, results.len())));
+    }
+    Ok(Outcome::Success)
+}
+
+// `update` follows project pins, so a pinned language would not move to the latest release.
+fn is_pinned(report: &Report) -> bool {
+    let Some(requested) = &report.requested else {
+        return false;
+    };
+    let manager = find_manager(&report.language).expect("report language is registered");
+    Version::parse(manager.version_scheme(), requested).is_ok()
+}
+
+fn describe_upgrade(report: &Report) -> String {
+    let provider = report.provider.map(|provider| format!(" ({})", provider)).unwrap_or_default();
+    format!(
+        "{} {} -> {}{}",
+        report.language,
+        report.installed.as_deref().unwrap_or("?"),
+        report.latest.as_deref().unwrap_or("?"),
+        provider
+    )
+}
+
+fn assume_yes(matches: &ArgMatches) -> bool {
+    matches.get_flag("yes") || !settings::get().confirm
+}
+
+fn handle_config(matches: &ArgMatches) -> Result<Outcome> {
+    let config = &settings::get().config;
+    let key = |matches: &ArgMatches| matches.get_one::<String>("key").expect("key is required").clone();
+
     match matches.subcommand() {
-        Some(("go", sub_matches)) => handle_go_commands(sub_matches).await,
-        Some(("rust", sub_matches)) => handle_rust_commands(sub_matches).await,
-        Some(("python", sub_matches)) => handle_python_commands(sub_matches).await,
-        Some(("node", sub_matches)) => handle_node_commands(sub_matches).await,
-        Some(("java", sub_matches)) => handle_java_commands(sub_matches).await,
-        _ => println!("Run 'language-revamp --help' for usage instructions."),
+        Some(("list", _)) => {
+            let entries = config.entries();
+            if is_structured() {
+                emit(&entries)?;
+            } else {
+                say!("# {}", config_file().display());
+                let key_width = entries.iter().map(|entry| entry.key.len()).max().unwrap_or(0);
+                let value_width = entries
+                    .iter()
+                    .map(|entry| entry.value.as_deref().unwrap_or("-").len())
+                    .max()
+                    .unwrap_or(0);
+                for entry in &entries {
+                    say!(
+                        "{:<key_width$}  {:<value_width$}  ({})",
+                        entry.key,
+                        entry.value.as_deref().unwrap_or("-"),
+                        describe_source(entry),
+                        key_width = key_width,
+                        value_width = value_width
+                    );
+                }
+            }
+        }
+        Some(("get", get_matches)) => {
+            let entry = config.entry(&key(get_matches))?;
+            if is_structured() {
+                emit(&entry)?;
+            } else {
+                match &entry.value {
+                    Some(value) => say!("{}", value),
+                    None => return Err(Error::Other(format!("{} is not set", entry.key))),
+                }
+            }
+        }
+        Some(("set", set_matches)) => {
+            let key = key(set_matches);
+            let value = set_matches.get_one::<String>("value").expect("value is required");
+            if is_dry_run() {
+                plan(format!("would set {} = {} in {}", key, value, config_file().display()));
+            } else {
+                let value = config::set(&key, value)?;
+                say!("Set {} = {} in {}", key, value, config_file().display());
+            }
+        }
+        Some(("unset", unset_matches)) => {
+            let key = key(unset_matches);
+            if is_dry_run() {
+                plan(format!("would remove {} from {}", key, config_file().display()));
+            } else {
+                config::unset(&key)?;
+                say!("Removed {} from {}", key, config_file().display());
+            }
+        }
+        _ => return Err(usage_error()),
+    }
+    Ok(Outcome::Success)
+}
+
+fn describe_source(entry: &Entry) -> String {
+    match entry.source {
+        Source::Environment => format!(
+            "env {}",
+            config::environment_variable(entry.key).unwrap_or_else(|| config::variable_name(entry.key))
+        ),
+        source => source.to_string(),
+    }
+}
+
+fn reshim() -> Result<Outcome> {
+    let dir = language_revamp::reshim()?;
+    say!("Shims written to {}", dir.display());
+    print_path_hint();
+    Ok(Outcome::Success)
+}
+
+fn offline_error() -> Error {
+    Error::Usage("--offline only answers check, status, outdated and list-remote; installing needs the network.".to_string())
+}
+
+fn usage_error() -> Error {
+    Error::Usage("Run 'language-revamp --help' for usage instructions.".to_string())
+}
+
+async fn handle_language_commands(manager: &dyn LanguageManager, matches: &ArgMatches) -> Result<Outcome> {
+    let check = matches.get_flag("check");
+    let update = matches.get_flag("update");
+    let install = matches.contains_id("install");
+    let uninstall = matches.contains_id("uninstall");
+
+    if settings::get().offline && (update || install) {
+        return Err(offline_error());
+    }
+
+    match matches.subcommand() {
+        Some(("list", _)) => return list_versions(manager).await,
+        Some(("list-remote", _)) => return list_remote_versions(manager).await,
+        Some(("use", use_matches)) => {
+            let version = use_matches.get_one::<String>("version").expect("version is required");
+            manager.use_version(version).await?;
+            return Ok(Outcome::Success);
+        }
+        _ => {}
+    }
+
+    let (action, result) = if check {
+        (Action::Check, run_check(manager).await)
+    } else if update {
+        (Action::Update, run_update(manager).await)
+    } else if install {
+        let version = matches.get_one::<String>("install").map(String::as_str);
+        (Action::Install, run_install(manager, version).await)
+    } else if uninstall {
+        let version = matches.get_one::<String>("uninstall").map(String::as_str);
+        (Action::Uninstall, run_uninstall(manager, version, assume_yes(matches)).await)
+    } else {
+        return Err(Error::Usage(format!(
+            "Run 'language-revamp {} --help' for usage instructions.",
+            manager.name()
+        )));
+    };
+
+    if is_structured() && (action == Action::Check || result.is_err()) {
+        emit(&Report::collect(manager, action, result.as_ref().err(), true).await)?;
+    }
+    result
+}
+
+async fn run_check(manager: &dyn LanguageManager) -> Result<Outcome> {
+    let name = manager.display_name();
+    say!("Checking {} version...", name);
+    let check = language_revamp::check(manager.name()).await?;
+    let installed = &check.installation.version;
+    say!("{} {} found at {}.", name, installed, check.installation.path.display());
+
+    match &check.verdict {
+        Verdict::Satisfies { requested, source } => {
+            say!("{} {} satisfies {} requested by {}.", name, installed, requested, source);
+        }
+        Verdict::Mismatch { requested, source } => {
+            say!("{} {} is active but {} requests {}.", name, installed, source, requested);
+        }
+        Verdict::Outdated { latest } => {
+            say!("A newer {} version is available: {} (installed {}).", name, latest, installed);
+        }
+        Verdict::UpToDate => say!("{} is up to date ({}).", name, installed),
+        Verdict::Unknown { reason } => eprintln!("Could not determine the latest {} version: {}", name, reason),
+    }
+    Ok(check.outcome())
+}
+
+async fn run_update(manager: &dyn LanguageManager) -> Result<Outcome> {
+    say!("Updating {}...", manager.display_name());
+    let report = language_revamp::update(manager.name()).await?;
+    if is_structured() {
+        emit(&report)?;
+    }
+    Ok(Outcome::Success)
+}
+
+async fn run_install(manager: &dyn LanguageManager, version: Option<&str>) -> Result<Outcome> {
+    say!("Installing {}...", manager.display_name());
+    let report = language_revamp::install(manager.name(), version).await?;
+    if is_structured() {
+        emit(&report)?;
+    }
+    Ok(Outcome::Success)
+}
+
+async fn run_uninstall(manager: &dyn LanguageManager, version: Option<&str>, assume_yes: bool) -> Result<Outcome> {
+    say!("Uninstalling {}...", manager.display_name());
+    let prompt = format!("Uninstall {}?", manager.display_name());
+    let report = language_revamp::uninstall(manager.name(), version, |_| Ok(assume_yes || confirm(&prompt)?)).await?;
+    if is_structured() {
+        emit(&report)?;
+    }
+    Ok(Outcome::Success)
+}
+
+async fn list_versions(manager: &dyn LanguageManager) -> Result<Outcome> {
+    let versions = language_revamp::list_installed(manager.name()).await?;
+    if versions.is_empty() {
+        say!("No {} versions installed.", manager.display_name());
+        return Err(Error::NotInstalled(manager.display_name().to_string()));
+    }
+
+    for installed in versions {
+        let marker = if installed.default { "*" } else { " " };
+        say!("{} {}", marker, installed.version);
+    }
+    Ok(Outcome::Success)
+}
+
+async fn list_remote_versions(manager: &dyn LanguageManager) -> Result<Outcome> {
+    let versions = language_revamp::list_releases(manager.name()).await?;
+    if is_structured() {
+        emit(&versions)?;
+    } else if versions.is_empty() {
+        say!("No {} releases found.", manager.display_name());
+    } else {
+        for remote in &versions {
+            match &remote.note {
+                Some(note) => say!("{} ({})", remote.version, note),
+                None => say!("{}", remote.version),
+            }
+        }
+    }
+    Ok(Outcome::Success)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<OsString> {
+        values.iter().map(OsString::from).collect()
+    }
+
+    #[test]
+    fn shim_arguments_reach_the_tool_unchanged() {
+        for forwarded in [&["-h"][..], &["--help"], &["--format", "x"], &["--dry-run", "build"], &["--root", "/tmp", "-v"]] {
+            let mut argv = vec!["language-revamp", "--root", "/opt/lr", "exec", "go"];
+            argv.extend_from_slice(forwarded);
+            assert_eq!(
+                shim_call(&args(&argv)),
+                Some(ShimCall {
+                    root: Some(PathBuf::from("/opt/lr")),
+                    binary: "go".to_string(),
+                    args: args(forwarded),
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn only_the_shim_form_is_an_exec() {
+        let call = shim_call(&args(&["language-revamp", "exec", "node"])).unwrap();
+        assert_eq!(call.root, None);
+        assert!(call.args.is_empty());
+
+        assert_eq!(shim_call(&args(&["language-revamp", "go", "--check"])), None);
+        assert_eq!(shim_call(&args(&["language-revamp", "--root", "/opt/lr", "status"])), None);
+        assert_eq!(shim_call(&args(&["language-revamp", "exec"])), None);
+        assert_eq!(shim_call(&args(&["language-revamp"])), None);
     }
 }
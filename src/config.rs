@@ -0,0 +1,371 @@
+//! Settings read from the configuration file and `LANGUAGE_REVAMP_*` environment variables.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::time::Duration;
+use serde::Serialize;
+use toml::{Table, Value};
+use crate::error::{Error, Result};
+use crate::utils::paths::{config_file, default_install_root};
+
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    Path,
+    Url,
+    Proxy,
+    List,
+    Duration,
+    Count,
+    Bool,
+    Choice(&'static [&'static str]),
+}
+
+struct Key {
+    name: &'static str,
+    kind: Kind,
+    default: Option<&'static str>,
+}
+
+const PROXY_SCHEMES: [&str; 4] = ["http", "https", "socks5", "socks5h"];
+
+const KEYS: &[Key] = &[
+    Key { name: "root", kind: Kind::Path, default: None },
+    Key { name: "system", kind: Kind::Bool, default: Some("false") },
+    Key { name: "confirm", kind: Kind::Bool, default: Some("true") },
+    Key { name: "cache.ttl", kind: Kind::Duration, default: Some("6h") },
+    Key { name: "go.metadata_url", kind: Kind::Url, default: Some("https://go.dev/dl/") },
+    Key { name: "go.download_url", kind: Kind::Url, default: Some("https://go.dev/dl/") },
+    Key { name: "java.channel", kind: Kind::Choice(&["lts", "latest"]), default: Some("lts") },
+    Key { name: "java.metadata_url", kind: Kind::Url, default: Some("https://api.adoptium.net/") },
+    Key { name: "java.download_url", kind: Kind::Url, default: Some("https://github.com/adoptium/") },
+    Key { name: "node.channel", kind: Kind::Choice(&["lts", "current"]), default: Some("lts") },
+    Key { name: "node.provider", kind: Kind::Choice(&["auto", "nvm", "tarball"]), default: Some("auto") },
+    Key { name: "node.metadata_url", kind: Kind::Url, default: Some("https://nodejs.org/dist/") },
+    Key { name: "node.download_url", kind: Kind::Url, default: Some("https://nodejs.org/dist/") },
+    Key { name: "python.provider", kind: Kind::Choice(&["auto", "pyenv", "conda", "system"]), default: Some("auto") },
+    Key { name: "python.metadata_url", kind: Kind::Url, default: Some("https://www.python.org/") },
+    Key { name: "rust.channel", kind: Kind::Choice(&["stable", "beta", "nightly"]), default: Some("stable") },
+    Key { name: "rust.metadata_url", kind: Kind::Url, default: Some("https://static.rust-lang.org/") },
+    Key { name: "rust.download_url", kind: Kind::Url, default: Some("https://static.rust-lang.org/") },
+    Key { name: "http.connect_timeout", kind: Kind::Duration, default: Some("10s") },
+    Key { name: "http.read_timeout", kind: Kind::Duration, default: Some("30s") },
+    Key { name: "http.retries", kind: Kind::Count, default: Some("3") },
+    Key { name: "proxy.https", kind: Kind::Proxy, default: None },
+    Key { name: "proxy.http", kind: Kind::Proxy, default: None },
+    Key { name: "proxy.no_proxy", kind: Kind::List, default: None },
+    Key { name: "tls.ca_bundle", kind: Kind::Path, default: None },
+    Key { name: "tls.client_cert", kind: Kind::Path, default: None },
+    Key { name: "tls.client_key", kind: Kind::Path, default: None },
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Source {
+    Default,
+    File,
+    Environment,
+    Flag,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Source::Default => "default",
+            Source::File => "file",
+            Source::Environment => "env",
+            Source::Flag => "flag",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Entry {
+    pub key: &'static str,
+    pub value: Option<String>,
+    pub source: Source,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    values: BTreeMap<&'static str, (String, Source)>,
+    warnings: Vec<String>,
+}
+
+impl Config {
+    pub fn load() -> Result<Config> {
+        let mut config = Config::default();
+        for (name, value) in flatten(&read_file()?) {
+            let key = find_key(&name).ok_or_else(|| {
+                Error::Parse(format!("Unknown setting '{}' in {}", name, config_file().display()))
+            })?;
+            let value = validate(key, &value)
+                .map_err(|e| Error::Parse(format!("{}: {}", config_file().display(), e)))?;
+            config.values.insert(key.name, (value, Source::File));
+        }
+
+        for key in KEYS {
+            if let Some(variable) = environment_variable(key.name) {
+                let value = env::var(&variable).unwrap_or_default();
+                match validate(key, &value) {
+                    Ok(value) => {
+                        config.values.insert(key.name, (value, Source::Environment));
+                    }
+                    Err(e) => config.warnings.push(format!("ignoring {}: {}", variable, e)),
+                }
+            }
+        }
+        Ok(config)
+    }
+
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    pub fn override_with(&mut self, name: &str, value: impl Into<String>) {
+        if let Some(key) = find_key(name) {
+            self.values.insert(key.name, (value.into(), Source::Flag));
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<String> {
+        match self.values.get(name) {
+            Some((value, _)) => Some(value.clone()),
+            None => default_value(find_key(name)?),
+        }
+    }
+
+    pub fn is_set(&self, name: &str) -> bool {
+        self.values.contains_key(name)
+    }
+
+    pub fn get_bool(&self, name: &str) -> bool {
+        self.get(name).is_some_and(|value| value == "true")
+    }
+
+    pub fn get_duration(&self, name: &str) -> Option<Duration> {
+        self.get(name).and_then(|value| parse_duration(&value))
+    }
+
+    pub fn entries(&self) -> Vec<Entry> {
+        KEYS.iter()
+            .map(|key| match self.values.get(key.name) {
+                Some((value, source)) => Entry { key: key.name, value: Some(value.clone()), source: *source },
+                None => Entry { key: key.name, value: default_value(key), source: Source::Default },
+            })
+            .collect()
+    }
+
+    pub fn entry(&self, name: &str) -> Result<Entry> {
+        let key = require_key(name)?;
+        Ok(self
+            .entries()
+            .into_iter()
+            .find(|entry| entry.key == key.name)
+            .expect("every key has an entry"))
+    }
+}
+
+pub fn set(name: &str, value: &str) -> Result<String> {
+    let key = require_key(name)?;
+    let normalized = validate(key, value).map_err(Error::Usage)?;
+    let value = match key.kind {
+        Kind::Bool => Value::Boolean(normalized == "true"),
+        _ => Value::String(normalized.clone()),
+    };
+
+    let mut table = read_file()?;
+    match key.name.split_once('.') {
+        Some((section, field)) => {
+            let section = table
+                .entry(section)
+                .or_insert_with(|| Value::Table(Table::new()))
+                .as_table_mut()
+                .ok_or_else(|| Error::Parse(format!("'{}' in {} is not a table", section, config_file().display())))?;
+            section.insert(field.to_string(), value);
+        }
+        None => {
+            table.insert(key.name.to_string(), value);
+        }
+    }
+    write_file(&table)?;
+    Ok(normalized)
+}
+
+pub fn unset(name: &str) -> Result<()> {
+    let key = require_key(name)?;
+    let mut table = read_file()?;
+    match key.name.split_once('.') {
+        Some((section, field)) => {
+            if let Some(Value::Table(fields)) = table.get_mut(section) {
+                fields.remove(field);
+                if fields.is_empty() {
+                    table.remove(section);
+                }
+            }
+        }
+        None => {
+            table.remove(key.name);
+        }
+    }
+    write_file(&table)
+}
+
+pub fn variable_name(name: &str) -> String {
+    format!("LANGUAGE_REVAMP_{}", name.to_ascii_uppercase().replace('.', "_"))
+}
+
+pub fn environment_variable(name: &str) -> Option<String> {
+    let standard: &[&str] = match name {
+        "proxy.https" => &["HTTPS_PROXY", "https_proxy"],
+        "proxy.http" => &["HTTP_PROXY", "http_proxy"],
+        "proxy.no_proxy" => &["NO_PROXY", "no_proxy"],
+        _ => &[],
+    };
+    std::iter::once(variable_name(name))
+        .chain(standard.iter().map(|variable| variable.to_string()))
+        .find(|variable| env::var(variable).is_ok_and(|value| !value.is_empty()))
+}
+
+pub fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim().to_ascii_lowercase();
+    let split = value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split);
+    let amount: u64 = amount.parse().ok()?;
+    let seconds = match unit.trim() {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        "d" => 86400,
+        _ => return None,
+    };
+    amount.checked_mul(seconds).map(Duration::from_secs)
+}
+
+fn read_file() -> Result<Table> {
+    let path = config_file();
+    match fs::read_to_string(&path) {
+        Ok(contents) => contents
+            .parse::<Table>()
+            .map_err(|e| Error::Parse(format!("{}: {}", path.display(), e))),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Table::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn write_file(table: &Table) -> Result<()> {
+    let path = config_file();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let contents = toml::to_string(table).map_err(|e| Error::Other(e.to_string()))?;
+    fs::write(&path, contents)?;
+    Ok(())
+}
+
+fn flatten(table: &Table) -> Vec<(String, String)> {
+    let mut values = Vec::new();
+    for (name, value) in table {
+        match value {
+            Value::Table(fields) => {
+                for (field, value) in fields {
+                    values.push((format!("{}.{}", name, field), scalar(value)));
+                }
+            }
+            value => values.push((name.clone(), scalar(value))),
+        }
+    }
+    values
+}
+
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn find_key(name: &str) -> Option<&'static Key> {
+    KEYS.iter().find(|key| key.name == name)
+}
+
+fn require_key(name: &str) -> Result<&'static Key> {
+    find_key(name).ok_or_else(|| {
+        Error::Usage(format!(
+            "Unknown setting '{}'. Known settings: {}",
+            name,
+            KEYS.iter().map(|key| key.name).collect::<Vec<_>>().join(", ")
+        ))
+    })
+}
+
+fn validate(key: &Key, value: &str) -> std::result::Result<String, String> {
+    let value = value.trim();
+    match key.kind {
+        Kind::Path if !value.is_empty() => Ok(value.to_string()),
+        Kind::Url if value.starts_with("https://") || value.starts_with("http://") => Ok(value.to_string()),
+        Kind::Url => Err(format!("{} expects an http(s) URL, got '{}'", key.name, value)),
+        Kind::Proxy => match value.split_once("://") {
+            Some((scheme, _)) if PROXY_SCHEMES.contains(&scheme.to_ascii_lowercase().as_str()) => Ok(value.to_string()),
+            Some(_) => Err(format!(
+                "{} expects an http, https, socks5 or socks5h proxy URL, got '{}'",
+                key.name, value
+            )),
+            None if !value.is_empty() => Ok(format!("http://{}", value)),
+            None => Err(format!("{} expects a proxy address such as proxy:3128", key.name)),
+        },
+        Kind::List if !value.is_empty() => Ok(value.split(',').map(str::trim).collect::<Vec<_>>().join(",")),
+        Kind::List => Err(format!("{} expects a comma-separated list", key.name)),
+        Kind::Duration if parse_duration(value).is_some() => Ok(value.to_ascii_lowercase()),
+        Kind::Duration => Err(format!("{} expects a duration such as 90s, 30m, 6h or 1d, got '{}'", key.name, value)),
+        Kind::Count => value
+            .parse::<u32>()
+            .map(|count| count.to_string())
+            .map_err(|_| format!("{} expects a whole number, got '{}'", key.name, value)),
+        Kind::Bool => match value.to_ascii_lowercase().as_str() {
+            "true" | "yes" | "1" | "on" => Ok("true".to_string()),
+            "false" | "no" | "0" | "off" => Ok("false".to_string()),
+            _ => Err(format!("{} expects true or false, got '{}'", key.name, value)),
+        },
+        Kind::Choice(choices) if choices.contains(&value) => Ok(value.to_string()),
+        Kind::Choice(choices) => Err(format!("{} expects one of {}, got '{}'", key.name, choices.join(", "), value)),
+        Kind::Path => Err(format!("{} expects a path", key.name)),
+    }
+}
+
+fn default_value(key: &Key) -> Option<String> {
+    match key.name {
+        "root" => Some(default_install_root().display().to_string()),
+        _ => key.default.map(str::to_string),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proxy(value: &str) -> std::result::Result<String, String> {
+        validate(find_key("proxy.https").unwrap(), value)
+    }
+
+    #[test]
+    fn durations_that_overflow_are_rejected() {
+        assert_eq!(parse_duration("90s"), Some(Duration::from_secs(90)));
+        assert_eq!(parse_duration("2d"), Some(Duration::from_secs(172800)));
+        assert_eq!(parse_duration("99999999999999999d"), None);
+        assert!(validate(find_key("cache.ttl").unwrap(), "99999999999999999d").is_err());
+    }
+
+    #[test]
+    fn proxies_are_accepted_the_way_reqwest_reads_them() {
+        assert_eq!(proxy("proxy:3128").unwrap(), "http://proxy:3128");
+        assert_eq!(proxy("http://proxy:3128").unwrap(), "http://proxy:3128");
+        assert_eq!(proxy("https://proxy").unwrap(), "https://proxy");
+        assert_eq!(proxy("socks5://127.0.0.1:1080").unwrap(), "socks5://127.0.0.1:1080");
+        assert_eq!(proxy("SOCKS5H://proxy").unwrap(), "SOCKS5H://proxy");
+        assert!(proxy("ftp://proxy").is_err());
+        assert!(proxy("").is_err());
+    }
+}
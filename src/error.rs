@@ -0,0 +1,134 @@
+//! The crate's error type and the outcomes the CLI turns into exit codes.
+
+use std::fmt;
+use std::io;
+
+pub const EXIT_SUCCESS: u8 = 0;
+pub const EXIT_FAILURE: u8 = 1;
+pub const EXIT_USAGE: u8 = 2;
+pub const EXIT_NOT_INSTALLED: u8 = 3;
+pub const EXIT_OUTDATED: u8 = 4;
+pub const EXIT_NETWORK: u8 = 5;
+pub const EXIT_CHECKSUM_MISMATCH: u8 = 6;
+pub const EXIT_PERMISSION: u8 = 7;
+pub const EXIT_UNSUPPORTED_OS: u8 = 8;
+pub const EXIT_PARSE: u8 = 9;
+pub const EXIT_COMMAND_FAILED: u8 = 10;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    NotInstalled(String),
+    Network(String),
+    ChecksumMismatch {
+        file: String,
+        expected: String,
+        actual: String,
+    },
+    Permission(String),
+    UnsupportedOs(String),
+    Parse(String),
+    CommandFailed {
+        command: String,
+        message: String,
+    },
+    Usage(String),
+    Io(io::Error),
+    Other(String),
+}
+
+impl Error {
+    pub fn command_failed(command: impl Into<String>, message: impl Into<String>) -> Self {
+        Error::CommandFailed {
+            command: command.into(),
+            message: message.into(),
+        }
+    }
+
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Error::NotInstalled(_) => EXIT_NOT_INSTALLED,
+            Error::Network(_) => EXIT_NETWORK,
+            Error::ChecksumMismatch { .. } => EXIT_CHECKSUM_MISMATCH,
+            Error::Permission(_) => EXIT_PERMISSION,
+            Error::UnsupportedOs(_) => EXIT_UNSUPPORTED_OS,
+            Error::Parse(_) => EXIT_PARSE,
+            Error::CommandFailed { .. } => EXIT_COMMAND_FAILED,
+            Error::Usage(_) => EXIT_USAGE,
+            Error::Io(_) | Error::Other(_) => EXIT_FAILURE,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotInstalled(language) => write!(f, "{} is not installed or not found in PATH", language),
+            Error::Network(message) => write!(f, "Network error: {}", message),
+            Error::ChecksumMismatch { file, expected, actual } => write!(
+                f,
+                "Checksum mismatch for {}: expected {}, got {}",
+                file, expected, actual
+            ),
+            Error::Permission(message) => write!(f, "Permission denied: {}", message),
+            Error::UnsupportedOs(operation) => write!(f, "Unsupported OS for {}", operation),
+            Error::Parse(message) => write!(f, "Parse error: {}", message),
+            Error::CommandFailed { command, message } => write!(f, "`{}` failed: {}", command, message),
+            Error::Usage(message) => write!(f, "{}", message),
+            Error::Io(e) => write!(f, "{}", e),
+            Error::Other(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        match e.kind() {
+            io::ErrorKind::PermissionDenied => Error::Permission(e.to_string()),
+            _ => Error::Io(e),
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Self {
+        Error::Network(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::Parse(e.to_string())
+    }
+}
+
+impl From<tokio::task::JoinError> for Error {
+    fn from(e: tokio::task::JoinError) -> Self {
+        Error::Other(e.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Success,
+    Outdated,
+}
+
+impl Outcome {
+    pub fn exit_code(self) -> u8 {
+        match self {
+            Outcome::Success => EXIT_SUCCESS,
+            Outcome::Outdated => EXIT_OUTDATED,
+        }
+    }
+}
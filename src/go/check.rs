@@ -1,30 +1,53 @@
-﻿use std::error::Error;
-use std::process::Command as ProcessCommand;
-use tokio::task;
-use which::which;
-
-pub async fn check_go_version() -> Result<(), Box<dyn Error + Send + Sync>> {
-    task::spawn_blocking(|| {
-        match which("go") {
-            Ok(path) => {
-                println!("Go found at: {}", path.display());
-                match ProcessCommand::new("go").arg("version").output() {
-                    Ok(output) => {
-                        let version_info = String::from_utf8_lossy(&output.stdout);
-                        println!("Installed Go version: {}", version_info.trim());
-                        Ok(())
-                    }
-                    Err(e) => {
-                        eprintln!("Error: Unable to retrieve Go version. {}", e);
-                        Err(Box::new(e) as Box<dyn Error + Send + Sync>)
-                    }
-                }
-            }
-            Err(e) => {
-                eprintln!("Go is not installed or not found in PATH. {}", e);
-                Err(Box::new(e) as Box<dyn Error + Send + Sync>)
-            }
-        }
-    })
-        .await?
+use crate::error::{Error, Result};
+use crate::output::say;
+use crate::utils::runner::CommandRunner;
+
+pub async fn check_go_version(runner: &dyn CommandRunner) -> Result<String> {
+    let path = runner.locate("go").ok_or_else(|| Error::NotInstalled("Go".to_string()))?;
+    say!("Go found at: {}", path.display());
+
+    let output = runner.output("go", &["version"])?;
+    if !output.success {
+        return Err(Error::command_failed("go version", output.stderr.trim()));
+    }
+
+    let version_info = output.stdout.trim();
+    say!("Installed Go version: {}", version_info);
+    Ok(version_info.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::go::update::get_installed_go_version;
+    use crate::utils::runner::ScriptedRunner;
+
+    const DEBIAN: &str = include_str!("../../tests/fixtures/go/go-version-debian.txt");
+    const GCCGO: &str = include_str!("../../tests/fixtures/go/go-version-gccgo.txt");
+
+    fn go(banner: &str) -> ScriptedRunner {
+        ScriptedRunner::new().stdout("go version", banner)
+    }
+
+    #[tokio::test]
+    async fn installed_version_is_the_third_field() {
+        assert_eq!(get_installed_go_version(&go(DEBIAN)).await.unwrap(), "go1.19.8");
+        assert_eq!(get_installed_go_version(&go(GCCGO)).await.unwrap(), "go1.18");
+    }
+
+    #[tokio::test]
+    async fn check_reports_the_whole_banner() {
+        assert_eq!(check_go_version(&go(DEBIAN)).await.unwrap(), "go version go1.19.8 linux/amd64");
+    }
+
+    #[tokio::test]
+    async fn missing_failing_and_unexpected_go_are_reported() {
+        assert!(matches!(check_go_version(&ScriptedRunner::new()).await, Err(Error::NotInstalled(_))));
+        assert!(matches!(get_installed_go_version(&ScriptedRunner::new()).await, Err(Error::NotInstalled(_))));
+
+        let broken = ScriptedRunner::new().failing("go version", "go: cannot find GOROOT directory: /usr/lib/go");
+        assert!(matches!(check_go_version(&broken).await, Err(Error::CommandFailed { .. })));
+        assert!(matches!(get_installed_go_version(&broken).await, Err(Error::CommandFailed { .. })));
+        assert!(matches!(get_installed_go_version(&go("go version devel\n")).await, Err(Error::Parse(_))));
+    }
 }
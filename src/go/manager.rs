@@ -0,0 +1,93 @@
+use async_trait::async_trait;
+use which::which;
+use crate::error::{Error, Result};
+use crate::go::check::check_go_version;
+use crate::go::update::{
+    fetch_latest_go_version, get_installed_go_version, go_layout, go_toolchain, install_go, list_go_releases,
+    resolve_go_version,
+};
+use crate::manager::{find_installation, Installation, LanguageManager, RemoteVersion};
+use crate::output::say;
+use crate::settings;
+use crate::utils::fetcher::MetadataFetcher;
+use crate::utils::install::{managed_uninstall_steps, Layout};
+use crate::utils::runner::SystemRunner;
+use crate::utils::uninstall::{toolchain_steps, Step};
+use crate::utils::version::Scheme;
+
+pub struct GoManager;
+
+#[async_trait]
+impl LanguageManager for GoManager {
+    fn name(&self) -> &'static str {
+        "go"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Go"
+    }
+
+    fn version_scheme(&self) -> Scheme {
+        Scheme::Go
+    }
+
+    fn layout(&self) -> Option<Layout> {
+        Some(go_layout())
+    }
+
+    fn version_files(&self) -> &'static [&'static str] {
+        &[".go-version"]
+    }
+
+    fn tool_versions_name(&self) -> &'static str {
+        "golang"
+    }
+
+    fn normalize_version(&self, version: &str) -> String {
+        if version.starts_with("go") {
+            version.to_string()
+        } else {
+            format!("go{}", version)
+        }
+    }
+
+    async fn check(&self) -> Result<()> {
+        check_go_version(&SystemRunner).await.map(|_| ())
+    }
+
+    async fn detect_installed(&self) -> Result<Installation> {
+        let path = which("go").map_err(|_| Error::NotInstalled("Go".to_string()))?;
+        let version = get_installed_go_version(&SystemRunner).await?;
+        Ok(Installation { version, path })
+    }
+
+    async fn fetch_latest(&self) -> Result<String> {
+        fetch_latest_go_version(&MetadataFetcher).await
+    }
+
+    async fn resolve_version(&self, requested: &str) -> Result<String> {
+        resolve_go_version(&MetadataFetcher, requested).await
+    }
+
+    async fn install(&self, version: Option<&str>) -> Result<()> {
+        let version = match version {
+            Some(v) => resolve_go_version(&MetadataFetcher, v).await?,
+            None => fetch_latest_go_version(&MetadataFetcher).await?,
+        };
+        say!("Downloading and installing Go version: {}", version);
+        install_go(&version).await
+    }
+
+    async fn list_remote(&self) -> Result<Vec<RemoteVersion>> {
+        list_go_releases(&MetadataFetcher).await
+    }
+
+    async fn uninstall_plan(&self, version: Option<&str>) -> Result<Vec<Step>> {
+        if !settings::get().system_wide {
+            return managed_uninstall_steps(&go_layout(), version.map(|v| self.normalize_version(v)).as_deref());
+        }
+
+        let installation = find_installation(self, version).await?;
+        toolchain_steps(&SystemRunner, &go_toolchain(), &installation)
+    }
+}
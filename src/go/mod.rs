@@ -1,3 +1,3 @@
-﻿pub(crate) mod check;
+pub(crate) mod check;
 pub(crate) mod update;
-pub (crate) mod cli;
\ No newline at end of file
+pub(crate) mod manager;
@@ -1,15 +1,34 @@
-﻿use std::error::Error;
-use std::fs;
+use std::env;
+use std::path::{Path, PathBuf};
 use std::process::Command as ProcessCommand;
-use reqwest;
 use serde_json::Value;
-use which::which;
+use crate::error::{Error, Result};
+use crate::manager::RemoteVersion;
+use crate::output::say;
+use crate::settings;
+use crate::utils::checksum::{record_digest, verify_sha256};
+use crate::utils::command::{remove_path, run};
+use crate::utils::download::download;
+use crate::utils::fetcher::{Fetcher, MetadataFetcher};
+use crate::utils::install::{install_into_root, install_system_wide, matches_version_prefix, Layout};
+use crate::utils::mirror::{download_url, metadata_url};
+use crate::utils::runner::CommandRunner;
 use crate::utils::system::{get_os, OS};
-use crate::utils::version::compare_versions;
+use crate::utils::uninstall::Toolchain;
+use crate::utils::version::{Scheme, Version};
 
-pub async fn fetch_latest_go_version() -> Result<String, Box<dyn Error + Send + Sync>> {
-    let response = reqwest::get("https://go.dev/dl/?mode=json").await?;
-    let body = response.text().await?;
+pub fn go_layout() -> Layout {
+    Layout {
+        language: "go",
+        scheme: Scheme::Go,
+        bin_subdir: "bin",
+        binaries: &["go", "gofmt"],
+        version_args: &["version"],
+    }
+}
+
+pub async fn fetch_latest_go_version(fetcher: &dyn Fetcher) -> Result<String> {
+    let body = fetcher.fetch(&metadata_url("go", "?mode=json")).await?;
 
     let releases: Vec<Value> = serde_json::from_str(&body)?;
     if let Some(latest_version) = releases.iter().find(|release| {
@@ -17,144 +36,277 @@ pub async fn fetch_latest_go_version() -> Result<String, Box<dyn Error + Send +
     }) {
         let version = latest_version["version"]
             .as_str()
-            .ok_or_else(|| -> Box<dyn Error + Send + Sync> { "Invalid version format".into() })?
+            .ok_or_else(|| Error::Parse("Invalid version format".to_string()))?
             .to_string();
         Ok(version)
     } else {
-        Err("Version not found".into())
+        Err(Error::Parse("No stable Go release found".to_string()))
+    }
+}
+
+pub async fn list_go_releases(fetcher: &dyn Fetcher) -> Result<Vec<RemoteVersion>> {
+    let body = fetcher.fetch(&metadata_url("go", "?mode=json&include=all")).await?;
+
+    let releases: Vec<Value> = serde_json::from_str(&body)?;
+    Ok(releases
+        .iter()
+        .filter_map(|release| {
+            let version = release["version"].as_str()?;
+            let stable = release["stable"].as_bool().unwrap_or(false);
+            Some(RemoteVersion {
+                version: version.to_string(),
+                note: (!stable).then(|| "unstable".to_string()),
+            })
+        })
+        .collect())
+}
+
+pub async fn resolve_go_version(fetcher: &dyn Fetcher, requested: &str) -> Result<String> {
+    let requested = if requested.starts_with("go") {
+        requested.to_string()
+    } else {
+        format!("go{}", requested)
+    };
+    if Version::parse(Scheme::Go, &requested)?.is_complete() {
+        return Ok(requested);
+    }
+
+    let body = fetcher.fetch(&metadata_url("go", "?mode=json&include=all")).await?;
+
+    let releases: Vec<Value> = serde_json::from_str(&body)?;
+    releases
+        .iter()
+        .filter(|release| release["stable"].as_bool().unwrap_or(false))
+        .filter_map(|release| release["version"].as_str())
+        .filter(|version| matches_version_prefix(version, &requested))
+        .filter_map(|version| Version::parse(Scheme::Go, version).ok().map(|parsed| (parsed, version)))
+        .max_by(|a, b| a.0.cmp(&b.0))
+        .map(|(_, version)| version.to_string())
+        .ok_or_else(|| Error::Other(format!("No Go release matches {}", requested)))
+}
+
+pub async fn fetch_go_checksum(fetcher: &dyn Fetcher, version: &str, file_name: &str) -> Result<String> {
+    let url = metadata_url("go", "?mode=json&include=all");
+    let mut releases: Vec<Value> = serde_json::from_str(&fetcher.fetch(&url).await?)?;
+    if !releases.iter().any(|release| release["version"].as_str() == Some(version)) {
+        // The full list is cached apart from the latest one and may predate the release it reported.
+        releases = serde_json::from_str(&fetcher.refetch(&url).await?)?;
+    }
+    releases
+        .iter()
+        .filter(|release| release["version"].as_str() == Some(version))
+        .filter_map(|release| release["files"].as_array())
+        .flatten()
+        .find(|file| file["filename"].as_str() == Some(file_name))
+        .and_then(|file| file["sha256"].as_str())
+        .map(str::to_string)
+        .ok_or_else(|| Error::Parse(format!("No published SHA-256 for {}", file_name)))
+}
+
+pub async fn get_installed_go_version(runner: &dyn CommandRunner) -> Result<String> {
+    if runner.locate("go").is_none() {
+        return Err(Error::NotInstalled("Go".to_string()));
+    }
+
+    let output = runner.output("go", &["version"])?;
+    if !output.success {
+        return Err(Error::command_failed("go version", output.stderr.trim()));
+    }
+    parse_go_version_output(output.stdout.trim())
+}
+
+fn parse_go_version_output(version_info: &str) -> Result<String> {
+    version_info
+        .split_whitespace()
+        .nth(2)
+        .filter(|version| version.starts_with("go") && version.len() > 2)
+        .map(str::to_string)
+        .ok_or_else(|| Error::Parse(format!("Could not determine installed Go version from '{}'", version_info)))
+}
+
+pub fn go_toolchain() -> Toolchain {
+    Toolchain {
+        name: "Go",
+        binaries: go_layout().binaries,
+        markers: &["VERSION", "src/runtime"],
+        uninstaller: None,
+        conda_package: "go",
+        workspaces: go_workspaces,
     }
 }
 
-async fn get_installed_go_version() -> Result<String, Box<dyn Error + Send + Sync>> {
-    match which("go") {
-        Ok(_) => {
-            let output = ProcessCommand::new("go").arg("version").output()?;
-            let version_info = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            let parts: Vec<&str> = version_info.split_whitespace().collect();
-            if parts.len() >= 3 {
-                Ok(parts[2].to_string())
-            } else {
-                Err("Could not determine installed Go version".into())
-            }
+fn go_workspaces(runner: &dyn CommandRunner) -> Vec<PathBuf> {
+    let mut workspaces = Vec::new();
+    if let Ok(output) = runner.output("go", &["env", "GOPATH", "GOMODCACHE"])
+        && output.success
+    {
+        for line in output.stdout.lines() {
+            workspaces.extend(env::split_paths(line.trim()).filter(|path| !path.as_os_str().is_empty()));
         }
-        Err(_) => Err("Go is not installed".into()),
     }
+    if let Some(gopath) = env::var_os("GOPATH") {
+        workspaces.extend(env::split_paths(&gopath));
+    }
+    workspaces.extend(dirs::home_dir().map(|home| home.join("go")));
+    workspaces
 }
 
-fn uninstall_go() -> Result<(), Box<dyn Error + Send + Sync>> {
+pub async fn install_go(version: &str) -> Result<()> {
     let os = get_os();
+    let system_wide = settings::get().system_wide;
 
-    let potential_paths = match os {
-        OS::Windows => vec![
-            "C:\\Program Files\\Go".to_string(),
-            "C:\\Go".to_string(),
-        ],
-        OS::Linux => vec![
-            "/usr/local/go".to_string(),
-            "$HOME/go".to_string(),
-        ],
-        OS::MacOS => vec![
-            "/usr/local/go".to_string(),
-            "/opt/go".to_string(),
-            "$HOME/go".to_string(),
-        ],
-        OS::Unknown => return Err("Unsupported OS for Go uninstallation".into()),
+    let file_name = match os {
+        OS::Windows if system_wide => format!("{}.windows-amd64.msi", version),
+        OS::Windows => format!("{}.windows-amd64.zip", version),
+        OS::Linux => format!("{}.linux-amd64.tar.gz", version),
+        OS::MacOS => format!("{}.darwin-amd64.tar.gz", version),
+        OS::Unknown => return Err(Error::UnsupportedOs("Go installation".to_string())),
     };
+    let download_url = download_url("go", &file_name);
 
-    let mut go_path_found = false;
+    say!("Downloading Go from {}", download_url);
 
-    for path in potential_paths {
-        if fs::metadata(&path).is_ok() {
-            println!("Go path found: {}", path);
-            fs::remove_dir_all(&path)?;
-            println!("Go has been removed from: {}", path);
-            go_path_found = true;
-            break;
-        }
+    let expected = fetch_go_checksum(&MetadataFetcher, version, &file_name).await?;
+    let archive = download(&download_url, &file_name).await?;
+    let digest = verify_sha256(&archive, &expected)?;
+    record_digest("go", version, &file_name, &digest)?;
+
+    say!("Installing Go...");
+
+    if !system_wide {
+        let result = install_into_root(&go_layout(), version, &archive);
+        remove_path(&archive)?;
+        result?;
+        say!("Go {} installed successfully!", version);
+        return Ok(());
     }
 
-    if !go_path_found {
-        return Err("Go installation path not found in any of the checked locations.".into());
+    let result = match os {
+        OS::Windows => run(ProcessCommand::new("msiexec").arg("/i").arg(&archive).args(["/quiet", "/norestart"]))
+            .and_then(|status| {
+                if status.success() {
+                    Ok(())
+                } else {
+                    Err(Error::command_failed("go installer", format!("exited with {}", status)))
+                }
+            }),
+        OS::Linux | OS::MacOS => install_system_wide(&go_layout(), version, &archive, Path::new("/usr/local/go")),
+        OS::Unknown => Err(Error::UnsupportedOs("Go installation".to_string())),
+    };
+    if let Err(e) = result {
+        remove_path(&archive)?;
+        return Err(e);
     }
 
+    remove_path(&archive)?;
+    say!("Go {} installed successfully!", version);
     Ok(())
 }
 
-pub async fn install_go(version: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
-    let os = get_os();
-
-    let download_url = match os {
-        OS::Windows => format!("https://go.dev/dl/{}.windows-amd64.msi", version),
-        OS::Linux => format!("https://go.dev/dl/{}.linux-amd64.tar.gz", version),
-        OS::MacOS => format!("https://go.dev/dl/{}.darwin-amd64.tar.gz", version),
-        OS::Unknown => return Err("Unsupported OS for Go installation".into()),
-    };
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::fetcher::FixtureFetcher;
+    use crate::utils::runner::ScriptedRunner;
 
-    println!("Downloading Go from {}", download_url);
+    const RELEASES: &str = include_str!("../../tests/fixtures/go/releases.json");
+    const ALL_RELEASES: &str = include_str!("../../tests/fixtures/go/releases-all.json");
 
-    let response = reqwest::get(&download_url).await?;
-    let bytes = response.bytes().await?;
+    fn fixtures(current: &str, all: &str) -> FixtureFetcher {
+        FixtureFetcher::new()
+            .with(metadata_url("go", "?mode=json"), current)
+            .with(metadata_url("go", "?mode=json&include=all"), all)
+    }
 
-    let file_name = if os == OS::Windows {
-        "go-installer.msi"
-    } else {
-        "go.tar.gz"
-    };
+    #[tokio::test]
+    async fn latest_is_the_newest_stable_release() {
+        let fetcher = fixtures(RELEASES, ALL_RELEASES);
+        assert_eq!(fetch_latest_go_version(&fetcher).await.unwrap(), "go1.23.4");
+    }
 
-    fs::write(file_name, &bytes)?;
+    #[tokio::test]
+    async fn latest_skips_release_candidates() {
+        let fetcher = fixtures(ALL_RELEASES, ALL_RELEASES);
+        assert_eq!(fetch_latest_go_version(&fetcher).await.unwrap(), "go1.23.4");
+    }
 
-    println!("Installing Go...");
+    #[tokio::test]
+    async fn partial_versions_resolve_to_the_newest_stable_patch() {
+        let fetcher = fixtures(RELEASES, ALL_RELEASES);
+        assert_eq!(resolve_go_version(&fetcher, "1.22").await.unwrap(), "go1.22.10");
+        assert_eq!(resolve_go_version(&fetcher, "go1.21").await.unwrap(), "go1.21.13");
+        assert_eq!(resolve_go_version(&fetcher, "1.21.5").await.unwrap(), "go1.21.5");
+        assert!(matches!(resolve_go_version(&fetcher, "1.2").await, Err(Error::Other(_))));
+        assert!(matches!(resolve_go_version(&fetcher, "1.24").await, Err(Error::Other(_))));
+    }
 
-    match os {
-        OS::Windows => {
-            ProcessCommand::new("msiexec")
-                .args(["/i", file_name, "/quiet", "/norestart"])
-                .status()?;
-        }
-        OS::Linux | OS::MacOS => {
-            ProcessCommand::new("sudo")
-                .args(["tar", "-C", "/usr/local", "-xzf", file_name])
-                .status()?;
-        }
-        OS::Unknown => {
-            return Err("Unsupported OS for Go installation".into());
-        }
+    #[tokio::test]
+    async fn remote_list_flags_unstable_releases() {
+        let releases = list_go_releases(&fixtures(RELEASES, ALL_RELEASES)).await.unwrap();
+        assert_eq!(releases.len(), 6);
+        assert_eq!(releases[0].version, "go1.24rc1");
+        assert_eq!(releases[0].note.as_deref(), Some("unstable"));
+        assert!(releases[1..].iter().all(|release| release.note.is_none()));
     }
 
-    fs::remove_file(file_name)?;
-    println!("Go {} installed successfully!", version);
-    Ok(())
-}
+    #[tokio::test]
+    async fn checksum_comes_from_the_matching_file() {
+        let fetcher = fixtures(RELEASES, ALL_RELEASES);
+        assert_eq!(
+            fetch_go_checksum(&fetcher, "go1.23.4", "go1.23.4.linux-amd64.tar.gz").await.unwrap(),
+            "829e9fbb0a66eba7458e1f6cf3b0744bb7b31b86fe9c77ebd76e3b595d50da99"
+        );
+        assert!(matches!(
+            fetch_go_checksum(&fetcher, "go1.23.4", "go1.23.4.plan9-386.tar.gz").await,
+            Err(Error::Parse(_))
+        ));
+    }
 
-pub async fn update_go() -> Result<(), Box<dyn Error + Send + Sync>> {
-    let installed_version = get_installed_go_version().await.unwrap_or_else(|_| "None".to_string());
+    #[tokio::test]
+    async fn stale_release_lists_are_refetched_for_checksums() {
+        let url = metadata_url("go", "?mode=json&include=all");
+        let fetcher = fixtures(RELEASES, "[]").refreshed(url, ALL_RELEASES);
+        assert_eq!(
+            fetch_go_checksum(&fetcher, "go1.23.4", "go1.23.4.linux-amd64.tar.gz").await.unwrap(),
+            "829e9fbb0a66eba7458e1f6cf3b0744bb7b31b86fe9c77ebd76e3b595d50da99"
+        );
+        assert!(matches!(
+            fetch_go_checksum(&fetcher, "go1.99.0", "go1.99.0.linux-amd64.tar.gz").await,
+            Err(Error::Parse(_))
+        ));
+    }
 
-    let latest_version = fetch_latest_go_version().await?;
+    #[tokio::test]
+    async fn empty_release_lists_are_reported() {
+        let fetcher = fixtures("[]", "[]");
+        assert!(matches!(fetch_latest_go_version(&fetcher).await, Err(Error::Parse(_))));
+        assert!(matches!(resolve_go_version(&fetcher, "1.22").await, Err(Error::Other(_))));
+        assert!(list_go_releases(&fetcher).await.unwrap().is_empty());
+    }
 
-    if installed_version == "None" {
-        println!("Go is not installed. Installing the latest version...");
-        install_go(&latest_version).await?;
-    } else {
-        match compare_versions(&installed_version, &latest_version) {
-            std::cmp::Ordering::Equal => {
-                println!("Go is already up to date ({}).", installed_version);
-            }
-            std::cmp::Ordering::Less => {
-                println!(
-                    "Updating Go from {} to {}...",
-                    installed_version, latest_version
-                );
-                uninstall_go()?;
-                install_go(&latest_version).await?;
-            }
-            std::cmp::Ordering::Greater => {
-                println!(
-                    "Your installed Go version ({}) is newer than the latest available ({}).",
-                    installed_version, latest_version
-                );
-            }
+    #[tokio::test]
+    async fn malformed_payloads_are_parse_errors() {
+        for body in ["<!DOCTYPE html><title>Go</title>", r#"{"version":"go1.23.4"}"#, r#"[{"version":1,"stable":true}]"#] {
+            let fetcher = fixtures(body, body);
+            assert!(
+                matches!(fetch_latest_go_version(&fetcher).await, Err(Error::Parse(_))),
+                "accepted {}",
+                body
+            );
         }
     }
 
-    Ok(())
+    #[tokio::test]
+    async fn fetch_failures_propagate() {
+        assert!(matches!(fetch_latest_go_version(&FixtureFetcher::new()).await, Err(Error::Network(_))));
+    }
+
+    #[test]
+    fn workspaces_include_the_go_env_paths() {
+        let runner = ScriptedRunner::new().stdout("go env GOPATH GOMODCACHE", "/srv/gopath\n/srv/gopath/pkg/mod\n");
+        let workspaces = go_workspaces(&runner);
+        assert!(workspaces.contains(&PathBuf::from("/srv/gopath")));
+        assert!(workspaces.contains(&PathBuf::from("/srv/gopath/pkg/mod")));
+        assert!(!go_workspaces(&ScriptedRunner::new()).contains(&PathBuf::from("/srv/gopath")));
+    }
 }
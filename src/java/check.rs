@@ -1,39 +1,83 @@
-﻿use std::process::Command;
-use which::which;
+use crate::error::{Error, Result};
+use crate::java::update::{java_version_output, version_line};
+use crate::output::{say, warn};
+use crate::utils::runner::CommandRunner;
 use crate::utils::system::{get_os, OS};
 
-pub async fn check_java_version() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    tokio::task::spawn_blocking(|| {
-        match which("java") {
-            Ok(path) => {
-                println!("Java found at: {}", path.display());
-                let output = match get_os() {
-                    OS::Windows => Command::new("cmd").args(&["/C", "java", "-version"]).output(),
-                    OS::Linux | OS::MacOS => Command::new("java").arg("-version").output(),
-                    OS::Unknown => Err(std::io::Error::new(std::io::ErrorKind::Other, "Unsupported OS")),
-                }?;
-
-                if output.status.success() {
-                    let version_info = String::from_utf8_lossy(&output.stderr);
-                    let version_line = version_info.lines().next().unwrap_or("Unknown version");
-                    println!("Installed Java version: {}", version_line);
-                    Ok(())
-                } else {
-                    let error_info = String::from_utf8_lossy(&output.stderr);
-                    eprintln!("Java execution failed: {}", error_info);
-                    Err("Java execution failed".into())
-                }
-            }
-            Err(e) => {
-                let suggestion = match get_os() {
-                    OS::Windows => "Ensure Java is installed and added to PATH (e.g., C:\\Program Files\\Java).",
-                    OS::Linux => "Ensure Java is installed (e.g., via package manager or /usr/bin).",
-                    OS::MacOS => "Ensure Java is installed (e.g., via Homebrew or /usr/bin).",
-                    OS::Unknown => "Ensure Java is installed on your system.",
-                };
-                eprintln!("Java is not installed or not found in PATH: {}. {}", e, suggestion);
-                Err(Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
-            }
+pub async fn check_java_version(runner: &dyn CommandRunner) -> Result<String> {
+    match runner.locate("java") {
+        Some(path) => {
+            say!("Java found at: {}", path.display());
+            let version_info = java_version_output(runner)?;
+            let version_line = version_line(&version_info).unwrap_or("Unknown version");
+            say!("Installed Java version: {}", version_line);
+            Ok(version_line.to_string())
         }
-    }).await?
-}
\ No newline at end of file
+        None => {
+            let suggestion = match get_os() {
+                OS::Windows => "Ensure Java is installed and added to PATH (e.g., C:\\Program Files\\Java).",
+                OS::Linux => "Ensure Java is installed (e.g., via package manager or /usr/bin).",
+                OS::MacOS => "Ensure Java is installed (e.g., via Homebrew or /usr/bin).",
+                OS::Unknown => "Ensure Java is installed on your system.",
+            };
+            warn(suggestion);
+            Err(Error::NotInstalled("Java".to_string()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::java::update::get_installed_java_version;
+    use crate::utils::runner::ScriptedRunner;
+    use crate::utils::version::{Scheme, Version};
+
+    const OPENJDK: &str = include_str!("../../tests/fixtures/java/java-version-openjdk17-debian.txt");
+    const ORACLE: &str = include_str!("../../tests/fixtures/java/java-version-oracle21.txt");
+    const TEMURIN_8: &str = include_str!("../../tests/fixtures/java/java-version-temurin8.txt");
+    const TOOL_OPTIONS: &str = include_str!("../../tests/fixtures/java/java-version-tool-options.txt");
+
+    fn java(banner: &str) -> ScriptedRunner {
+        ScriptedRunner::new()
+            .stderr("java -version", banner)
+            .stderr("cmd /C java -version", banner)
+    }
+
+    #[tokio::test]
+    async fn installed_version_comes_from_the_build_string() {
+        for (banner, expected, major) in [
+            (OPENJDK, "17.0.15+6-Debian-1deb12u1", 17),
+            (ORACLE, "21.0.2+13-LTS-58", 21),
+            (TEMURIN_8, "1.8.0_392-b08", 8),
+            (TOOL_OPTIONS, "17.0.15+6-Debian-1deb12u1", 17),
+        ] {
+            let version = get_installed_java_version(&java(banner)).await.unwrap();
+            assert_eq!(version, expected);
+            assert_eq!(Version::parse(Scheme::Java, &version).unwrap().major(), major);
+        }
+    }
+
+    #[tokio::test]
+    async fn check_reports_the_version_line() {
+        assert_eq!(
+            check_java_version(&java(ORACLE)).await.unwrap(),
+            r#"java version "21.0.2" 2024-01-16 LTS"#
+        );
+        assert_eq!(
+            check_java_version(&java(TOOL_OPTIONS)).await.unwrap(),
+            r#"openjdk version "17.0.15" 2025-04-15"#
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_and_failing_java_are_reported() {
+        assert!(matches!(check_java_version(&ScriptedRunner::new()).await, Err(Error::NotInstalled(_))));
+        assert!(matches!(get_installed_java_version(&ScriptedRunner::new()).await, Err(Error::NotInstalled(_))));
+
+        let broken = ScriptedRunner::new().failing("java -version", "Error: could not find libjava.so");
+        assert!(matches!(check_java_version(&broken).await, Err(Error::CommandFailed { .. })));
+        let garbled = java("Error occurred during initialization of VM\n");
+        assert!(matches!(get_installed_java_version(&garbled).await, Err(Error::Parse(_))));
+    }
+}
@@ -0,0 +1,83 @@
+use async_trait::async_trait;
+use which::which;
+use crate::error::{Error, Result};
+use crate::java::check::check_java_version;
+use crate::java::update::{
+    fetch_latest_java_version, get_installed_java_version, install_java, java_layout, java_toolchain,
+    list_java_releases, resolve_java_version,
+};
+use crate::manager::{find_installation, Installation, LanguageManager, RemoteVersion};
+use crate::settings;
+use crate::utils::fetcher::MetadataFetcher;
+use crate::utils::install::{managed_uninstall_steps, Layout};
+use crate::utils::runner::SystemRunner;
+use crate::utils::uninstall::{toolchain_steps, Step};
+use crate::utils::version::Scheme;
+
+pub struct JavaManager;
+
+#[async_trait]
+impl LanguageManager for JavaManager {
+    fn name(&self) -> &'static str {
+        "java"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Java"
+    }
+
+    fn version_scheme(&self) -> Scheme {
+        Scheme::Java
+    }
+
+    fn layout(&self) -> Option<Layout> {
+        Some(java_layout())
+    }
+
+    fn version_files(&self) -> &'static [&'static str] {
+        &[".java-version"]
+    }
+
+    fn normalize_version(&self, version: &str) -> String {
+        version.trim_start_matches(|c: char| !c.is_ascii_digit()).to_string()
+    }
+
+    async fn check(&self) -> Result<()> {
+        check_java_version(&SystemRunner).await.map(|_| ())
+    }
+
+    async fn detect_installed(&self) -> Result<Installation> {
+        let path = which("java").map_err(|_| Error::NotInstalled("Java".to_string()))?;
+        let version = get_installed_java_version(&SystemRunner).await?;
+        Ok(Installation { version, path })
+    }
+
+    async fn fetch_latest(&self) -> Result<String> {
+        fetch_latest_java_version(&MetadataFetcher).await
+    }
+
+    async fn resolve_version(&self, requested: &str) -> Result<String> {
+        resolve_java_version(&MetadataFetcher, requested).await
+    }
+
+    async fn install(&self, version: Option<&str>) -> Result<()> {
+        let version = match version {
+            Some(v) => resolve_java_version(&MetadataFetcher, v).await?,
+            None => fetch_latest_java_version(&MetadataFetcher).await?,
+        };
+        install_java(&version).await
+    }
+
+    async fn list_remote(&self) -> Result<Vec<RemoteVersion>> {
+        list_java_releases(&MetadataFetcher).await
+    }
+
+    async fn uninstall_plan(&self, version: Option<&str>) -> Result<Vec<Step>> {
+        if !settings::get().system_wide {
+            return managed_uninstall_steps(&java_layout(), version.map(|v| self.normalize_version(v)).as_deref());
+        }
+
+        let installation = find_installation(self, version).await?;
+        toolchain_steps(&SystemRunner, &java_toolchain(), &installation)
+    }
+}
@@ -1,190 +1,391 @@
-﻿use std::{fs, process::Command, env};
-use reqwest;
+use std::process::Command;
+use std::path::Path;
 use serde_json::{Value, from_str};
-use which::which;
+use crate::error::{Error, Result};
+use crate::manager::RemoteVersion;
+use crate::output::say;
+use crate::settings;
+use crate::utils::checksum::{record_digest, verify_sha256};
+use crate::utils::command::{remove_path, run};
+use crate::utils::download::download;
+use crate::utils::fetcher::{Fetcher, MetadataFetcher};
+use crate::utils::install::{install_into_root, install_system_wide, Layout};
+use crate::utils::mirror::{download_url, metadata_url};
+use crate::utils::runner::CommandRunner;
 use crate::utils::system::{get_os, OS};
-use crate::utils::version::compare_versions;
+use crate::utils::uninstall::Toolchain;
+use crate::utils::version::{Scheme, Version};
 
-pub async fn fetch_latest_java_version() -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
-    let response = reqwest::get("https://api.adoptium.net/v3/info/available_releases").await?;
-    let body = response.text().await?;
+pub fn java_layout() -> Layout {
+    Layout {
+        language: "java",
+        scheme: Scheme::Java,
+        bin_subdir: if get_os() == OS::MacOS { "Contents/Home/bin" } else { "bin" },
+        binaries: &["java", "javac", "jar", "jshell"],
+        version_args: &["-version"],
+    }
+}
+
+pub async fn fetch_latest_java_version(fetcher: &dyn Fetcher) -> Result<String> {
+    let body = fetcher.fetch(&metadata_url("java", "v3/info/available_releases")).await?;
 
     let release_info: Value = from_str(&body)?;
+    if settings::get().config.get("java.channel").as_deref() == Some("latest") {
+        let feature = release_info["most_recent_feature_release"]
+            .as_u64()
+            .ok_or_else(|| Error::Parse("No feature release found".to_string()))?;
+        return fetch_latest_java_release(fetcher, feature).await;
+    }
+
     let available_lts = release_info["available_lts_releases"]
         .as_array()
-        .ok_or("No LTS releases found")?;
+        .ok_or_else(|| Error::Parse("No LTS releases found".to_string()))?;
 
     if let Some(latest_lts) = available_lts.iter().max_by_key(|v| v.as_i64().unwrap_or(0)) {
-        let version = latest_lts.as_i64().unwrap().to_string();
-        Ok(version)
+        let feature = latest_lts
+            .as_u64()
+            .ok_or_else(|| Error::Parse("Invalid version format".to_string()))?;
+        fetch_latest_java_release(fetcher, feature).await
     } else {
-        Err("No LTS version found".into())
+        Err(Error::Parse("No LTS version found".to_string()))
     }
 }
 
-pub async fn install_java(version: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let os = get_os();
+pub async fn list_java_releases(fetcher: &dyn Fetcher) -> Result<Vec<RemoteVersion>> {
+    let body = fetcher.fetch(&metadata_url("java", "v3/info/available_releases")).await?;
 
-    let download_url = match os {
-        OS::Windows => format!(
-            "https://github.com/adoptium/temurin{}-binaries/releases/download/jdk-{}%2B37/OpenJDK{}U-jdk_x64_windows_hotspot_{}u37.msi",
-            version, version, version, version
-        ),
-        OS::Linux => format!(
-            "https://github.com/adoptium/temurin{}-binaries/releases/download/jdk-{}%2B37/OpenJDK{}U-jdk_x64_linux_hotspot_{}u37.tar.gz",
-            version, version, version, version
-        ),
-        OS::MacOS => format!(
-            "https://github.com/adoptium/temurin{}-binaries/releases/download/jdk-{}%2B37/OpenJDK{}U-jdk_x64_mac_hotspot_{}u37.tar.gz",
-            version, version, version, version
-        ),
-        OS::Unknown => return Err("Unsupported OS for Java installation".into()),
-    };
+    let release_info: Value = from_str(&body)?;
+    let lts: Vec<u64> = release_info["available_lts_releases"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_u64)
+        .collect();
+    let mut features: Vec<u64> = release_info["available_releases"]
+        .as_array()
+        .ok_or_else(|| Error::Parse("No Java releases found".to_string()))?
+        .iter()
+        .filter_map(Value::as_u64)
+        .collect();
+    features.sort_unstable_by(|a, b| b.cmp(a));
+    Ok(features
+        .into_iter()
+        .map(|feature| RemoteVersion {
+            version: feature.to_string(),
+            note: lts.contains(&feature).then(|| "LTS".to_string()),
+        })
+        .collect())
+}
+
+pub async fn fetch_latest_java_release(fetcher: &dyn Fetcher, feature: u64) -> Result<String> {
+    let body = fetcher.fetch(&latest_release_url(feature)?).await?;
 
-    println!("Downloading Java LTS v{} from {}", version, download_url);
+    let assets: Vec<Value> = from_str(&body)?;
+    let openjdk_version = assets
+        .first()
+        .and_then(|asset| asset["version"]["openjdk_version"].as_str())
+        .ok_or_else(|| Error::Parse(format!("No Java {} release found", feature)))?;
 
-    let response = reqwest::get(&download_url).await?;
-    let bytes = response.bytes().await?;
+    Ok(Version::parse(Scheme::Java, openjdk_version)?.to_string())
+}
 
-    let file_name = if os == OS::Windows {
-        "java-installer.msi"
+fn latest_release_url(feature: u64) -> Result<String> {
+    let os = match get_os() {
+        OS::Windows => "windows",
+        OS::Linux => "linux",
+        OS::MacOS => "mac",
+        OS::Unknown => return Err(Error::UnsupportedOs("Java installation".to_string())),
+    };
+    Ok(metadata_url(
+        "java",
+        &format!("v3/assets/latest/{}/hotspot?architecture=x64&image_type=jdk&os={}", feature, os),
+    ))
+}
+
+pub async fn resolve_java_version(fetcher: &dyn Fetcher, requested: &str) -> Result<String> {
+    if requested.chars().all(|c| c.is_ascii_digit()) {
+        fetch_latest_java_release(fetcher, Version::parse(Scheme::Java, requested)?.major()).await
     } else {
-        "java.tar.gz"
+        Ok(Version::parse(Scheme::Java, requested)?.to_string())
+    }
+}
+
+pub async fn fetch_java_checksum(fetcher: &dyn Fetcher, version: &str, file_name: &str) -> Result<String> {
+    let url = metadata_url(
+        "java",
+        &format!(
+            "v3/assets/release_name/eclipse/{}?architecture=x64&image_type=jdk",
+            release_name(&Version::parse(Scheme::Java, version)?).replace('+', "%2B")
+        ),
+    );
+    let body = fetcher.fetch(&url).await?;
+
+    let release: Value = from_str(&body)?;
+    release["binaries"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .flat_map(|binary| [&binary["package"], &binary["installer"]])
+        .find(|package| package["name"].as_str() == Some(file_name))
+        .and_then(|package| package["checksum"].as_str())
+        .map(str::to_string)
+        .ok_or_else(|| Error::Parse(format!("No published SHA-256 for {}", file_name)))
+}
+
+fn release_name(version: &Version) -> String {
+    match version.major() {
+        8 => format!("jdk8u{}-b{:02}", version.release().get(2).unwrap_or(&0), version.build().unwrap_or(0)),
+        _ => format!("jdk-{}", version),
+    }
+}
+
+fn file_version(version: &Version) -> String {
+    match version.major() {
+        8 => format!("8u{}b{:02}", version.release().get(2).unwrap_or(&0), version.build().unwrap_or(0)),
+        _ => version.to_string().replace('+', "_"),
+    }
+}
+
+pub async fn install_java(version: &str) -> Result<()> {
+    let os = get_os();
+    let system_wide = settings::get().system_wide;
+    let parsed = Version::parse(Scheme::Java, version)?;
+    let feature = parsed.major();
+    let tag = release_name(&parsed).replace('+', "%2B");
+    let file_version = file_version(&parsed);
+
+    let file_name = match os {
+        OS::Windows if system_wide => format!("OpenJDK{}U-jdk_x64_windows_hotspot_{}.msi", feature, file_version),
+        OS::Windows => format!("OpenJDK{}U-jdk_x64_windows_hotspot_{}.zip", feature, file_version),
+        OS::Linux => format!("OpenJDK{}U-jdk_x64_linux_hotspot_{}.tar.gz", feature, file_version),
+        OS::MacOS => format!("OpenJDK{}U-jdk_x64_mac_hotspot_{}.tar.gz", feature, file_version),
+        OS::Unknown => return Err(Error::UnsupportedOs("Java installation".to_string())),
     };
+    let download_url = download_url(
+        "java",
+        &format!("temurin{}-binaries/releases/download/{}/{}", feature, tag, file_name),
+    );
 
-    fs::write(file_name, &bytes)?;
+    say!("Downloading Java v{} from {}", version, download_url);
 
-    println!("Installing Java...");
+    let expected = fetch_java_checksum(&MetadataFetcher, version, &file_name).await?;
+    let archive = download(&download_url, &file_name).await?;
+    let digest = verify_sha256(&archive, &expected)?;
+    record_digest("java", version, &file_name, &digest)?;
+
+    say!("Installing Java...");
+
+    if !system_wide {
+        let result = install_into_root(&java_layout(), version, &archive);
+        remove_path(&archive)?;
+        result?;
+        say!("Java v{} installed successfully!", version);
+        return Ok(());
+    }
 
     match os {
         OS::Windows => {
-            let status = Command::new("msiexec")
-                .args(["/i", file_name, "/quiet", "/norestart"])
-                .status()?;
+            let status = run(Command::new("msiexec")
+                .arg("/i")
+                .arg(&archive)
+                .args(["/quiet", "/norestart"]))?;
             if !status.success() {
-                return Err("Failed to install Java MSI".into());
+                return Err(Error::command_failed("msiexec", format!("exited with {}", status)));
             }
         }
         OS::Linux | OS::MacOS => {
-            let status = Command::new("sudo")
-                .args(["tar", "-C", "/usr/local", "-xzf", file_name])
-                .status()?;
-            if !status.success() {
-                return Err("Failed to extract Java archive".into());
-            }
-
-            let extracted_dir = format!(
-                "/usr/local/jdk-{}+37",
-                version
-            );
-            Command::new("sudo")
-                .args(["ln", "-sf", &format!("{}/bin/java", extracted_dir), "/usr/local/bin/java"])
-                .status()?;
+            let extracted_dir = format!("/usr/local/jdk-{}", version);
+            install_system_wide(&java_layout(), version, &archive, Path::new(&extracted_dir))?;
+            run(Command::new("sudo")
+                .args(["ln", "-sf", &format!("{}/bin/java", extracted_dir), "/usr/local/bin/java"]))?;
         }
         OS::Unknown => {
-            return Err("Unsupported OS for Java installation".into());
+            return Err(Error::UnsupportedOs("Java installation".to_string()));
         }
     }
 
-    fs::remove_file(file_name)?;
+    remove_path(&archive)?;
 
-    println!("Java v{} installed successfully!", version);
+    say!("Java v{} installed successfully!", version);
     Ok(())
 }
 
-pub async fn get_installed_java_version() -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
-    tokio::task::spawn_blocking(|| {
-        match which("java") {
-            Ok(_) => {
-                let output = match get_os() {
-                    OS::Windows => Command::new("cmd").args(&["/C", "java", "-version"]).output(),
-                    OS::Linux | OS::MacOS => Command::new("java").arg("-version").output(),
-                    OS::Unknown => Err(std::io::Error::new(std::io::ErrorKind::Other, "Unsupported OS")),
-                }?;
-
-                if output.status.success() {
-                    let version_info = String::from_utf8_lossy(&output.stderr);
-                    let version_line = version_info.lines().next().unwrap_or("Unknown version");
-                    let version = version_line.split('"').nth(1).unwrap_or("0").split('.').next().unwrap_or("0");
-                    Ok(version.to_string())
-                } else {
-                    Err("Failed to get Java version".into())
-                }
-            }
-            Err(_) => Err("Java is not installed".into()),
-        }
-    }).await?
+pub async fn get_installed_java_version(runner: &dyn CommandRunner) -> Result<String> {
+    if runner.locate("java").is_none() {
+        return Err(Error::NotInstalled("Java".to_string()));
+    }
+    parse_java_version_output(&java_version_output(runner)?)
 }
 
-pub async fn update_java() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let installed_version = get_installed_java_version().await.unwrap_or("0".to_string());
-    let latest_version = fetch_latest_java_version().await?;
+pub fn java_version_output(runner: &dyn CommandRunner) -> Result<String> {
+    let output = match get_os() {
+        OS::Windows => runner.output("cmd", &["/C", "java", "-version"]),
+        OS::Linux | OS::MacOS => runner.output("java", &["-version"]),
+        OS::Unknown => return Err(Error::UnsupportedOs("Java version check".to_string())),
+    }?;
 
-    if installed_version == "0" {
-        println!("Java is not installed. Installing the latest LTS version...");
-        install_java(&latest_version).await?;
+    if output.success {
+        Ok(output.text().to_string())
     } else {
-        match compare_versions(&installed_version, &latest_version) {
-            std::cmp::Ordering::Equal => {
-                println!("Java is already up to date (v{})", installed_version);
-            }
-            std::cmp::Ordering::Less => {
-                println!("Updating Java from v{} to v{}", installed_version, latest_version);
-                uninstall_java()?;
-                install_java(&latest_version).await?;
-            }
-            std::cmp::Ordering::Greater => {
-                println!(
-                    "Installed Java version (v{}) is newer than the latest available (v{})",
-                    installed_version, latest_version
-                );
-            }
-        }
+        Err(Error::command_failed("java -version", output.stderr.trim()))
     }
+}
 
-    Ok(())
+pub fn version_line(version_info: &str) -> Option<&str> {
+    version_info.lines().map(str::trim).find(|line| line.contains(" version \""))
 }
 
-fn uninstall_java() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let os = get_os();
+fn parse_java_version_output(version_info: &str) -> Result<String> {
+    let quoted = version_line(version_info)
+        .and_then(|line| line.split('"').nth(1))
+        .ok_or_else(|| Error::Parse(format!("Could not determine installed Java version from '{}'", version_info.trim())))?;
 
-    match os {
-        OS::Windows => {
-            let status = Command::new("wmic")
-                .args(&["product", "where", "name like 'Java%'", "call", "uninstall"])
-                .status()?;
-            if !status.success() {
-                println!("Could not find Java installation to uninstall via MSI. You may need to uninstall manually.");
-            } else {
-                println!("Java uninstalled successfully via MSI.");
-            }
-            let java_paths = vec![
-                "C:\\Program Files\\Java".to_string(),
-                format!("{}\\Java", env::var("ProgramFiles").unwrap_or_default()),
-            ];
-            for path in java_paths {
-                if fs::metadata(&path).is_ok() {
-                    fs::remove_dir_all(&path)?;
-                    println!("Cleaned up Java directory: {}", path);
-                }
-            }
-            Ok(())
-        }
-        OS::Linux | OS::MacOS => {
-            let java_paths = vec![
-                "/usr/local/jdk-*".to_string(),
-                "/usr/local/bin/java".to_string(),
-            ];
-            for path in java_paths {
-                if fs::metadata(&path).is_ok() {
-                    Command::new("sudo")
-                        .args(["rm", "-rf", &path])
-                        .status()?;
-                    println!("Removed Java from: {}", path);
-                }
-            }
-            Ok(())
+    let build = version_info
+        .lines()
+        .filter_map(|line| line.split("(build ").nth(1))
+        .filter_map(|rest| rest.split(')').next())
+        .find(|build| build.starts_with(quoted));
+
+    Ok(build.unwrap_or(quoted).to_string())
+}
+
+pub fn java_toolchain() -> Toolchain {
+    Toolchain {
+        name: "Java",
+        binaries: java_layout().binaries,
+        markers: &["release", "lib"],
+        uninstaller: None,
+        conda_package: "openjdk",
+        workspaces: |_| Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manager::{update_step, UpdateStep};
+    use crate::utils::fetcher::FixtureFetcher;
+
+    const AVAILABLE: &str = include_str!("../../tests/fixtures/java/available_releases.json");
+    const LATEST_21: &str = include_str!("../../tests/fixtures/java/assets-latest-21.json");
+    const LATEST_17: &str = include_str!("../../tests/fixtures/java/assets-latest-17.json");
+    const LATEST_11: &str = include_str!("../../tests/fixtures/java/assets-latest-11.json");
+    const LATEST_8: &str = include_str!("../../tests/fixtures/java/assets-latest-8.json");
+    const RELEASE_21: &str = include_str!("../../tests/fixtures/java/release-jdk-21.0.5+11.json");
+
+    fn fixtures(available: &str) -> FixtureFetcher {
+        FixtureFetcher::new()
+            .with(metadata_url("java", "v3/info/available_releases"), available)
+            .with(latest_release_url(21).unwrap(), LATEST_21)
+            .with(latest_release_url(17).unwrap(), LATEST_17)
+            .with(latest_release_url(11).unwrap(), LATEST_11)
+            .with(latest_release_url(8).unwrap(), LATEST_8)
+            .with(
+                metadata_url(
+                    "java",
+                    "v3/assets/release_name/eclipse/jdk-21.0.5%2B11?architecture=x64&image_type=jdk",
+                ),
+                RELEASE_21,
+            )
+    }
+
+    #[tokio::test]
+    async fn latest_is_the_newest_release_of_the_highest_lts() {
+        assert_eq!(fetch_latest_java_version(&fixtures(AVAILABLE)).await.unwrap(), "21.0.5+11");
+    }
+
+    #[tokio::test]
+    async fn lts_list_order_does_not_matter() {
+        let shuffled = r#"{"available_lts_releases":[21,8,17,11],"available_releases":[8,11,17,21]}"#;
+        assert_eq!(fetch_latest_java_version(&fixtures(shuffled)).await.unwrap(), "21.0.5+11");
+    }
+
+    #[tokio::test]
+    async fn feature_numbers_resolve_to_their_latest_release() {
+        let fetcher = fixtures(AVAILABLE);
+        assert_eq!(resolve_java_version(&fetcher, "21").await.unwrap(), "21.0.5+11");
+        assert_eq!(resolve_java_version(&fetcher, "17").await.unwrap(), "17.0.13+11");
+        assert_eq!(resolve_java_version(&fetcher, "11").await.unwrap(), "11.0.25+9");
+        assert_eq!(resolve_java_version(&fetcher, "21.0.4+7").await.unwrap(), "21.0.4+7");
+    }
+
+    #[tokio::test]
+    async fn newer_builds_that_satisfy_a_pin_are_kept() {
+        let target = resolve_java_version(&fixtures(AVAILABLE), "17").await.unwrap();
+        assert_eq!(target, "17.0.13+11");
+        assert_eq!(
+            update_step(Scheme::Java, "17.0.15+6", &target, Some("17")).unwrap(),
+            UpdateStep::SatisfiesRequest
+        );
+        assert_eq!(update_step(Scheme::Java, "17.0.13+11", &target, Some("17")).unwrap(), UpdateStep::UpToDate);
+        assert_eq!(update_step(Scheme::Java, "17.0.12+7", &target, Some("17")).unwrap(), UpdateStep::Install);
+        assert_eq!(update_step(Scheme::Java, "21.0.5+11", &target, Some("17")).unwrap(), UpdateStep::Install);
+        assert_eq!(update_step(Scheme::Java, "17.0.15+6", &target, None).unwrap(), UpdateStep::AheadOfLatest);
+    }
+
+    #[tokio::test]
+    async fn java_8_keeps_the_upstream_release_spelling() {
+        let version = resolve_java_version(&fixtures(AVAILABLE), "8").await.unwrap();
+        assert_eq!(version, "1.8.0_432-b06");
+
+        let parsed = Version::parse(Scheme::Java, &version).unwrap();
+        assert_eq!(release_name(&parsed), "jdk8u432-b06");
+        assert_eq!(file_version(&parsed), "8u432b06");
+
+        let parsed = Version::parse(Scheme::Java, "21.0.5+11").unwrap();
+        assert_eq!(release_name(&parsed), "jdk-21.0.5+11");
+        assert_eq!(file_version(&parsed), "21.0.5_11");
+    }
+
+    #[tokio::test]
+    async fn remote_list_is_newest_first_with_lts_marked() {
+        let releases = list_java_releases(&fixtures(AVAILABLE)).await.unwrap();
+        let versions: Vec<&str> = releases.iter().map(|release| release.version.as_str()).collect();
+        assert_eq!(versions, ["23", "22", "21", "20", "19", "18", "17", "16", "11", "8"]);
+        assert_eq!(releases[2].note.as_deref(), Some("LTS"));
+        assert_eq!(releases[0].note, None);
+    }
+
+    #[tokio::test]
+    async fn checksum_covers_packages_and_installers() {
+        let fetcher = fixtures(AVAILABLE);
+        assert_eq!(
+            fetch_java_checksum(&fetcher, "21.0.5+11", "OpenJDK21U-jdk_x64_linux_hotspot_21.0.5_11.tar.gz")
+                .await
+                .unwrap(),
+            "eea56c45d06f367b3ad766228c6cedca23dacdf5536ad09d60e1172b9e0f4680"
+        );
+        assert_eq!(
+            fetch_java_checksum(&fetcher, "21.0.5+11", "OpenJDK21U-jdk_x64_windows_hotspot_21.0.5_11.msi")
+                .await
+                .unwrap(),
+            "d49a70dae96323ceb03c760bdbdcc0194351ba7c0219f21ca1f2d31053b4d15c"
+        );
+        assert!(matches!(
+            fetch_java_checksum(&fetcher, "21.0.5+11", "OpenJDK21U-jre_x64_linux_hotspot_21.0.5_11.tar.gz").await,
+            Err(Error::Parse(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn empty_release_lists_are_reported() {
+        let empty = r#"{"available_lts_releases":[],"available_releases":[]}"#;
+        assert!(matches!(fetch_latest_java_version(&fixtures(empty)).await, Err(Error::Parse(_))));
+        assert!(list_java_releases(&fixtures(empty)).await.unwrap().is_empty());
+
+        let fetcher = fixtures(AVAILABLE).with(latest_release_url(21).unwrap(), "[]");
+        assert!(matches!(fetch_latest_java_version(&fetcher).await, Err(Error::Parse(_))));
+    }
+
+    #[tokio::test]
+    async fn malformed_payloads_are_parse_errors() {
+        for body in ["", "[8, 11, 17, 21]", r#"{"available_lts_releases":"21"}"#, r#"{"available_lts_releases":["21"]}"#] {
+            assert!(
+                matches!(fetch_latest_java_version(&fixtures(body)).await, Err(Error::Parse(_))),
+                "accepted {}",
+                body
+            );
         }
-        OS::Unknown => Err("Unsupported OS for Java uninstallation".into()),
+        assert!(matches!(list_java_releases(&fixtures("{}")).await, Err(Error::Parse(_))));
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,217 @@
+//! Detect, install and update Go, Rust, Python, Node.js and Java toolchains.
+//!
+//! This crate is the engine behind the `language-revamp` command line tool. The functions at the
+//! crate root take a language name (`"go"`, `"rust"`, `"python"`, `"node"` or `"java"`, see
+//! [`languages`]) and return structured results instead of printing them:
+//!
+//! ```no_run
+//! # async fn example() -> language_revamp::error::Result<()> {
+//! let installed = language_revamp::detect("go").await?;
+//! println!("Go {} at {}", installed.version, installed.path.display());
+//!
+//! for release in language_revamp::list_releases("node").await?.iter().take(5) {
+//!     println!("{}", release.version);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Behaviour follows the same [`settings`] as the CLI: the install root, `--system`, `--dry-run`,
+//! `--offline` and the configuration file. Call [`settings::init`] once before anything else to
+//! change them; it fails once any function here has run. Otherwise they are read from the
+//! configuration file and the environment on first use, and a file that fails to load is
+//! ignored. Build them with [`config::Config::load`] and [`settings::Settings::from_config`] to
+//! see those errors and [`config::Config::warnings`].
+//!
+//! Long-running steps such as downloads narrate their progress on stdout and report retries on
+//! stderr; set [`settings::Settings::quiet`] to silence both. Nothing reads from stdin. Shims
+//! written by [`install`] and [`update`] run the current executable with the hidden `exec`
+//! subcommand, so outside the CLI run `language-revamp reshim` afterwards.
+
+mod go;
+mod java;
+mod nodejs;
+mod python;
+mod rust;
+
+pub mod config;
+pub mod error;
+pub mod manager;
+pub mod output;
+pub mod report;
+pub mod settings;
+pub mod utils;
+
+use std::cmp::Ordering;
+use std::path::PathBuf;
+use crate::error::{Error, Result};
+use crate::manager::{find_manager, registry, Installation, InstalledVersion, LanguageManager, RemoteVersion};
+use crate::report::{Action, Check, Report, Status, Verdict};
+use crate::utils::command::is_dry_run;
+use crate::utils::install::matches_version_prefix;
+use crate::utils::paths::shims_dir;
+use crate::utils::resolve::requested_version;
+use crate::utils::shims::write_shims;
+use crate::utils::uninstall::{execute, Step};
+use crate::utils::version::Version;
+
+/// Names accepted by the functions in this crate, in the order the CLI lists them.
+pub fn languages() -> Vec<&'static str> {
+    registry().iter().map(|manager| manager.name()).collect()
+}
+
+/// Finds the toolchain that is active on `PATH` and reports its version and location.
+///
+/// Fails with [`Error::NotInstalled`] when the language cannot be found.
+pub async fn detect(language: &str) -> Result<Installation> {
+    manager(language)?.detect_installed().await
+}
+
+/// Compares the active toolchain with the version the project requests or, when nothing is
+/// requested, with the latest release.
+///
+/// A failed release lookup is not an error; it yields [`Verdict::Unknown`] with the reason.
+pub async fn check(language: &str) -> Result<Check> {
+    let manager = manager(language)?;
+    let installation = manager.detect_installed().await?;
+
+    let verdict = match requested_version(manager.as_ref()) {
+        Some(requested) if Version::parse(manager.version_scheme(), &requested.version).is_ok() => {
+            let installed = manager.normalize_version(&installation.version);
+            let source = requested.source.to_string();
+            if matches_version_prefix(&installed, &requested.version) {
+                Verdict::Satisfies { requested: requested.version, source }
+            } else {
+                Verdict::Mismatch { requested: requested.version, source }
+            }
+        }
+        _ => match manager.fetch_latest().await {
+            Ok(latest) => {
+                let scheme = manager.version_scheme();
+                match Version::parse(scheme, &installation.version)?.cmp(&Version::parse(scheme, &latest)?) {
+                    Ordering::Less => Verdict::Outdated { latest },
+                    _ => Verdict::UpToDate,
+                }
+            }
+            Err(e) => Verdict::Unknown { reason: e.to_string() },
+        },
+    };
+
+    Ok(Check {
+        language: manager.name().to_string(),
+        installation,
+        verdict,
+    })
+}
+
+/// Lists the versions installed under the language-revamp root, marking the default one.
+pub async fn list_installed(language: &str) -> Result<Vec<InstalledVersion>> {
+    manager(language)?.list_installed().await
+}
+
+/// Lists the upstream releases that can be installed, newest first.
+pub async fn list_releases(language: &str) -> Result<Vec<RemoteVersion>> {
+    manager(language)?.list_remote().await
+}
+
+/// Installs `version`, or the version the project requests, or the latest release.
+///
+/// Partial versions such as `1.22` or `21` resolve to the newest matching release.
+pub async fn install(language: &str, version: Option<&str>) -> Result<Report> {
+    let manager = manager(language)?;
+    let version = match version {
+        Some(version) => Some(version.to_string()),
+        None => requested_version(manager.as_ref()).map(|requested| {
+            say!(
+                "Using {} {} requested by {}",
+                manager.display_name(),
+                requested.version,
+                requested.source
+            );
+            requested.version
+        }),
+    };
+    manager.install(version.as_deref()).await?;
+    Ok(Report::collect(manager.as_ref(), Action::Install, None, true).await)
+}
+
+/// Brings the toolchain up to the requested version or the latest release, installing it
+/// when it is missing.
+pub async fn update(language: &str) -> Result<Report> {
+    let manager = manager(language)?;
+    manager.update().await?;
+    Ok(Report::collect(manager.as_ref(), Action::Update, None, true).await)
+}
+
+/// Removes the toolchain, or only `version` when several are installed.
+///
+/// `approve` receives the paths and packages about to be removed and can cancel by returning
+/// `false`, which fails with [`Error::Other`]. It is not called during a dry run.
+pub async fn uninstall(
+    language: &str,
+    version: Option<&str>,
+    approve: impl FnOnce(&[Step]) -> Result<bool>,
+) -> Result<Report> {
+    let manager = manager(language)?;
+    let steps = manager.uninstall_plan(version).await?;
+    execute(manager.display_name(), &steps, approve)?;
+
+    if !is_dry_run()
+        && manager.layout().is_some()
+        && manager.global_version().is_none()
+        && let Some(remaining) = manager.list_installed().await?.last()
+    {
+        say!(
+            "No default {} version is set; run 'language-revamp {} use {}' to pick one.",
+            manager.display_name(),
+            manager.name(),
+            remaining.version
+        );
+    }
+    Ok(Report::collect(manager.as_ref(), Action::Uninstall, None, false).await)
+}
+
+/// Reports the installed and, if `include_latest` is set, the latest version of every language.
+pub async fn status(include_latest: bool) -> Result<Vec<Report>> {
+    let tasks: Vec<_> = registry()
+        .into_iter()
+        .map(|manager| {
+            tokio::spawn(async move { Report::collect(manager.as_ref(), Action::Status, None, include_latest).await })
+        })
+        .collect();
+
+    let mut reports = Vec::new();
+    for task in tasks {
+        reports.push(task.await?);
+    }
+    Ok(reports)
+}
+
+/// The reports from [`status`] whose installed version is behind the latest release.
+pub async fn outdated() -> Result<Vec<Report>> {
+    Ok(status(true)
+        .await?
+        .into_iter()
+        .filter(|report| report.status == Status::Outdated)
+        .collect())
+}
+
+/// Rewrites the shims of every language that has a default version and returns their directory.
+pub fn reshim() -> Result<PathBuf> {
+    for manager in registry() {
+        if manager.global_version().is_some() {
+            write_shims(manager.shim_binaries())?;
+        }
+    }
+    Ok(shims_dir())
+}
+
+fn manager(language: &str) -> Result<Box<dyn LanguageManager>> {
+    find_manager(language).ok_or_else(|| {
+        Error::Usage(format!(
+            "Unknown language '{}'. Known languages: {}",
+            language,
+            languages().join(", ")
+        ))
+    })
+}
@@ -1,15 +1,27 @@
-mod go;
-mod rust;
 mod cli;
-mod utils;
-mod python;
-mod nodejs;
-mod java;
 
-use cli::{build_cli, handle_cli};
+use std::env;
+use std::ffi::OsString;
+use std::process::ExitCode;
+use cli::{build_cli, handle_cli, handle_shim_call, shim_call};
+use language_revamp::error::{self, Error};
 
 #[tokio::main]
-async fn main() {
-    let matches = build_cli().get_matches();
-    handle_cli(matches).await;
+async fn main() -> ExitCode {
+    let args: Vec<OsString> = env::args_os().collect();
+    let result = match shim_call(&args) {
+        Some(call) => handle_shim_call(call),
+        None => handle_cli(build_cli().get_matches_from(args)).await,
+    };
+    match result {
+        Ok(outcome) => ExitCode::from(outcome.exit_code()),
+        Err(Error::Usage(message)) => {
+            println!("{}", message);
+            ExitCode::from(error::EXIT_USAGE)
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::from(e.exit_code())
+        }
+    }
 }
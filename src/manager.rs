@@ -0,0 +1,234 @@
+//! The [`LanguageManager`] trait implemented by every supported language, and its registry.
+
+use std::cmp::Ordering;
+use std::path::PathBuf;
+use async_trait::async_trait;
+use clap::{Arg, ArgMatches};
+use serde::Serialize;
+use crate::config::Config;
+use crate::error::{Error, Result};
+use crate::go::manager::GoManager;
+use crate::java::manager::JavaManager;
+use crate::nodejs::manager::NodeManager;
+use crate::output::say;
+use crate::python::manager::PythonManager;
+use crate::rust::manager::RustManager;
+use crate::settings;
+use crate::utils::install::{
+    default_version, installed_versions, managed_uninstall_steps, matches_version_prefix, resolve_installed_version,
+    set_default_version, Layout,
+};
+use crate::utils::paths::version_dir;
+use crate::utils::resolve::requested_version;
+use crate::utils::uninstall::Step;
+use crate::utils::version::{Scheme, Version};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Installation {
+    pub version: String,
+    pub path: PathBuf,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InstalledVersion {
+    pub version: String,
+    pub default: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RemoteVersion {
+    pub version: String,
+    pub note: Option<String>,
+}
+
+#[async_trait]
+pub trait LanguageManager: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn display_name(&self) -> &'static str;
+
+    fn version_scheme(&self) -> Scheme;
+
+    fn layout(&self) -> Option<Layout> {
+        None
+    }
+
+    fn shim_binaries(&self) -> &'static [&'static str] {
+        self.layout().map(|layout| layout.binaries).unwrap_or(&[])
+    }
+
+    fn version_files(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn tool_versions_name(&self) -> &'static str {
+        self.name()
+    }
+
+    fn global_version(&self) -> Option<String> {
+        self.layout().and_then(|layout| default_version(layout.language))
+    }
+
+    fn version_bin_dir(&self, version: &str) -> Result<PathBuf> {
+        let layout = self.layout().ok_or_else(|| not_managed(self.display_name()))?;
+        let resolved = resolve_installed_version(&layout, version)?;
+        Ok(version_dir(layout.language, &resolved).join(layout.bin_subdir))
+    }
+
+    fn normalize_version(&self, version: &str) -> String {
+        version.to_string()
+    }
+
+    fn extra_args(&self) -> Vec<Arg> {
+        Vec::new()
+    }
+
+    fn configure(&self, _matches: &ArgMatches, _config: &mut Config) -> Result<()> {
+        Ok(())
+    }
+
+    async fn check(&self) -> Result<()>;
+
+    async fn detect_installed(&self) -> Result<Installation>;
+
+    async fn fetch_latest(&self) -> Result<String>;
+
+    async fn resolve_version(&self, requested: &str) -> Result<String> {
+        Ok(self.normalize_version(requested))
+    }
+
+    async fn install(&self, version: Option<&str>) -> Result<()>;
+
+    async fn uninstall_plan(&self, version: Option<&str>) -> Result<Vec<Step>> {
+        let layout = self.layout().ok_or_else(|| not_managed(self.display_name()))?;
+        managed_uninstall_steps(&layout, version.map(|version| self.normalize_version(version)).as_deref())
+    }
+
+    async fn list_installed(&self) -> Result<Vec<InstalledVersion>> {
+        let layout = self.layout().ok_or_else(|| not_managed(self.display_name()))?;
+        let default = default_version(layout.language);
+        Ok(installed_versions(&layout)?
+            .into_iter()
+            .map(|version| InstalledVersion {
+                default: default.as_deref() == Some(version.as_str()),
+                version,
+            })
+            .collect())
+    }
+
+    async fn list_remote(&self) -> Result<Vec<RemoteVersion>>;
+
+    async fn use_version(&self, version: &str) -> Result<()> {
+        let layout = self.layout().ok_or_else(|| not_managed(self.display_name()))?;
+        let resolved = resolve_installed_version(&layout, &self.normalize_version(version))?;
+        set_default_version(&layout, &resolved)
+    }
+
+    async fn update(&self) -> Result<()> {
+        update_language(self).await
+    }
+}
+
+pub async fn update_language<M: LanguageManager + ?Sized>(manager: &M) -> Result<()> {
+    let name = manager.display_name();
+    let requested = requested_version(manager);
+    let target_version = match &requested {
+        Some(requested) => {
+            say!("{} {} requested by {}", name, requested.version, requested.source);
+            manager.resolve_version(&requested.version).await?
+        }
+        None => manager.fetch_latest().await?,
+    };
+
+    match manager.detect_installed().await {
+        Err(Error::NotInstalled(_)) => {
+            say!("{} is not installed. Installing version {}...", name, target_version);
+            manager.install(Some(&target_version)).await?;
+        }
+        Ok(installed) => {
+            say!("{} found at: {}", name, installed.path.display());
+            let pinned = requested.as_ref().map(|requested| requested.version.as_str());
+            let installed_version = manager.normalize_version(&installed.version);
+            match update_step(manager.version_scheme(), &installed_version, &target_version, pinned)? {
+                UpdateStep::UpToDate => {
+                    say!("{} is already up to date ({}).", name, installed.version);
+                }
+                UpdateStep::SatisfiesRequest => {
+                    say!(
+                        "{} {} already satisfies the requested {} (newest release: {}).",
+                        name,
+                        installed.version,
+                        pinned.unwrap_or_default(),
+                        target_version
+                    );
+                }
+                UpdateStep::AheadOfLatest => {
+                    say!(
+                        "Your installed {} version ({}) is newer than the latest available ({}).",
+                        name, installed.version, target_version
+                    );
+                }
+                UpdateStep::Install => {
+                    say!("Updating {} from {} to {}...", name, installed.version, target_version);
+                    manager.install(Some(&target_version)).await?;
+                    if !settings::get().system_wide && requested.is_none() {
+                        manager.use_version(&target_version).await?;
+                    }
+                }
+            }
+        }
+        Err(e) => return Err(e),
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum UpdateStep {
+    UpToDate,
+    SatisfiesRequest,
+    AheadOfLatest,
+    Install,
+}
+
+pub fn update_step(scheme: Scheme, installed: &str, target: &str, requested: Option<&str>) -> Result<UpdateStep> {
+    let ordering = Version::parse(scheme, installed)?.cmp(&Version::parse(scheme, target)?);
+    let satisfies_request = requested.is_some_and(|requested| matches_version_prefix(installed, requested));
+    Ok(match ordering {
+        Ordering::Equal => UpdateStep::UpToDate,
+        Ordering::Greater if satisfies_request => UpdateStep::SatisfiesRequest,
+        Ordering::Greater if requested.is_none() => UpdateStep::AheadOfLatest,
+        _ => UpdateStep::Install,
+    })
+}
+
+pub async fn find_installation<M: LanguageManager + ?Sized>(manager: &M, version: Option<&str>) -> Result<Installation> {
+    let installation = manager.detect_installed().await?;
+    if let Some(version) = version
+        && !matches_version_prefix(
+            &manager.normalize_version(&installation.version),
+            &manager.normalize_version(version),
+        )
+    {
+        return Err(Error::NotInstalled(format!("{} {}", manager.display_name(), version)));
+    }
+    Ok(installation)
+}
+
+fn not_managed(name: &str) -> Error {
+    Error::Other(format!("{} versions are not managed by language-revamp", name))
+}
+
+pub fn registry() -> Vec<Box<dyn LanguageManager>> {
+    vec![
+        Box::new(GoManager),
+        Box::new(RustManager),
+        Box::new(PythonManager),
+        Box::new(NodeManager),
+        Box::new(JavaManager),
+    ]
+}
+
+pub fn find_manager(name: &str) -> Option<Box<dyn LanguageManager>> {
+    registry().into_iter().find(|manager| manager.name() == name)
+}
@@ -1,27 +1,41 @@
-﻿use std::process::Command;
-use which::which;
+use crate::error::{Error, Result};
+use crate::output::say;
+use crate::utils::runner::CommandRunner;
 
-pub async fn check_node_version() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    tokio::task::spawn_blocking(|| {
-        match which("node") {
-            Ok(path) => {
-                println!("Node found at: {}", path.display());
-                match Command::new("node").arg("-v").output() {
-                    Ok(output) => {
-                        let version_info = String::from_utf8_lossy(&output.stdout);
-                        println!("Installed Node version: {}", version_info.trim());
-                        Ok(())
-                    }
-                    Err(e) => {
-                        eprintln!("Error getting Node version: {}", e);
-                        Err(Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
-                    }
-                }
-            }
-            Err(e) => {
-                eprintln!("Node is not installed or not found in PATH. {}", e);
-                Err(Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
-            }
-        }
-    }).await?
-}
\ No newline at end of file
+pub async fn check_node_version(runner: &dyn CommandRunner) -> Result<String> {
+    let path = runner.locate("node").ok_or_else(|| Error::NotInstalled("Node.js".to_string()))?;
+    say!("Node found at: {}", path.display());
+
+    let output = runner.output("node", &["-v"])?;
+    if !output.success {
+        return Err(Error::command_failed("node -v", output.stderr.trim()));
+    }
+
+    let version_info = output.stdout.trim();
+    say!("Installed Node version: {}", version_info);
+    Ok(version_info.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nodejs::update::get_installed_node_version;
+    use crate::utils::runner::ScriptedRunner;
+
+    const NODE: &str = include_str!("../../tests/fixtures/node/node-version.txt");
+
+    #[tokio::test]
+    async fn installed_version_drops_the_v_prefix() {
+        let runner = ScriptedRunner::new().stdout("node -v", NODE);
+        assert_eq!(check_node_version(&runner).await.unwrap(), "v22.12.0");
+        assert_eq!(get_installed_node_version(&runner).await.unwrap(), "22.12.0");
+    }
+
+    #[tokio::test]
+    async fn missing_and_failing_node_are_reported() {
+        assert!(matches!(check_node_version(&ScriptedRunner::new()).await, Err(Error::NotInstalled(_))));
+        let broken = ScriptedRunner::new().failing("node -v", "node: error while loading shared libraries");
+        assert!(matches!(check_node_version(&broken).await, Err(Error::CommandFailed { .. })));
+        assert!(matches!(get_installed_node_version(&broken).await, Err(Error::CommandFailed { .. })));
+    }
+}
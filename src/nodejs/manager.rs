@@ -0,0 +1,138 @@
+use async_trait::async_trait;
+use clap::{Arg, ArgMatches};
+use which::which;
+use crate::config::Config;
+use crate::error::{Error, Result};
+use crate::manager::{find_installation, update_language, Installation, LanguageManager, RemoteVersion};
+use crate::nodejs::check::check_node_version;
+use crate::nodejs::update::{
+    fetch_latest_node_version, get_installed_node_version, install_node, install_with_nvm,
+    is_nvm_installed, list_node_releases, node_layout, node_toolchain, nvm_uninstall_steps, resolve_node_version, update_with_nvm, use_nvm,
+};
+use crate::output::say;
+use crate::settings;
+use crate::utils::fetcher::MetadataFetcher;
+use crate::utils::install::{managed_uninstall_steps, Layout};
+use crate::utils::provider::{detect_provider, Provider};
+use crate::utils::resolve::requested_version;
+use crate::utils::runner::SystemRunner;
+use crate::utils::uninstall::{toolchain_steps, Step};
+use crate::utils::version::Scheme;
+
+pub struct NodeManager;
+
+#[async_trait]
+impl LanguageManager for NodeManager {
+    fn name(&self) -> &'static str {
+        "node"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Node.js"
+    }
+
+    fn version_scheme(&self) -> Scheme {
+        Scheme::Semver
+    }
+
+    fn layout(&self) -> Option<Layout> {
+        Some(node_layout())
+    }
+
+    fn version_files(&self) -> &'static [&'static str] {
+        &[".nvmrc", ".node-version"]
+    }
+
+    fn tool_versions_name(&self) -> &'static str {
+        "nodejs"
+    }
+
+    fn normalize_version(&self, version: &str) -> String {
+        version.trim_start_matches('v').to_string()
+    }
+
+    fn extra_args(&self) -> Vec<Arg> {
+        vec![
+            Arg::new("nvm")
+                .short('n')
+                .long("nvm")
+                .help("Use NVM (Node Version Manager) for updates/installation")
+                .action(clap::ArgAction::SetTrue),
+        ]
+    }
+
+    fn configure(&self, matches: &ArgMatches, config: &mut Config) -> Result<()> {
+        if matches.get_flag("nvm") {
+            if !is_nvm_installed() {
+                return Err(Error::NotInstalled("NVM".to_string()));
+            }
+            config.override_with("node.provider", "nvm");
+        }
+        Ok(())
+    }
+
+    async fn check(&self) -> Result<()> {
+        check_node_version(&SystemRunner).await.map(|_| ())
+    }
+
+    async fn detect_installed(&self) -> Result<Installation> {
+        let path = which("node").map_err(|_| Error::NotInstalled("Node.js".to_string()))?;
+        let version = get_installed_node_version(&SystemRunner).await?;
+        Ok(Installation { version, path })
+    }
+
+    async fn fetch_latest(&self) -> Result<String> {
+        fetch_latest_node_version(&MetadataFetcher).await
+    }
+
+    async fn resolve_version(&self, requested: &str) -> Result<String> {
+        resolve_node_version(&MetadataFetcher, requested).await
+    }
+
+    async fn install(&self, version: Option<&str>) -> Result<()> {
+        let version = match version {
+            Some(v) => resolve_node_version(&MetadataFetcher, v).await?,
+            None => fetch_latest_node_version(&MetadataFetcher).await?,
+        };
+        if use_nvm() {
+            install_with_nvm(&version).await
+        } else {
+            install_node(&version).await
+        }
+    }
+
+    async fn list_remote(&self) -> Result<Vec<RemoteVersion>> {
+        list_node_releases(&MetadataFetcher).await
+    }
+
+    async fn uninstall_plan(&self, version: Option<&str>) -> Result<Vec<Step>> {
+        if !settings::get().system_wide {
+            if !use_nvm() {
+                return managed_uninstall_steps(&node_layout(), version.map(|v| self.normalize_version(v)).as_deref());
+            }
+            if let Some(version) = version {
+                return Ok(nvm_uninstall_steps(version));
+            }
+        }
+
+        let installation = find_installation(self, version).await?;
+        match detect_provider(&SystemRunner, &installation.path) {
+            Provider::Nvm => Ok(nvm_uninstall_steps(version.unwrap_or(&installation.version))),
+            _ => toolchain_steps(&SystemRunner, &node_toolchain(), &installation),
+        }
+    }
+
+    async fn update(&self) -> Result<()> {
+        if !use_nvm() {
+            return update_language(self).await;
+        }
+        let version = match requested_version(self) {
+            Some(requested) => {
+                say!("Node.js {} requested by {}", requested.version, requested.source);
+                self.resolve_version(&requested.version).await?
+            }
+            None => self.fetch_latest().await?,
+        };
+        update_with_nvm(&version).await
+    }
+}
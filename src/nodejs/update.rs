@@ -1,276 +1,339 @@
-﻿use std::{fs, process::Command, env};
-use std::path::Path;
-use reqwest;
+﻿use std::{process::Command, env};
+use std::path::{Path, PathBuf};
 use serde_json::{Value, from_str};
-use which::which;
+use crate::error::{Error, Result};
+use crate::manager::RemoteVersion;
+use crate::output::say;
+use crate::settings;
+use crate::utils::checksum::{find_in_shasums, record_digest, verify_sha256};
+use crate::utils::command::{remove_path, run};
+use crate::utils::download::download;
+use crate::utils::fetcher::{Fetcher, MetadataFetcher};
+use crate::utils::install::{install_into_root, install_system_wide, matches_version_prefix, Layout};
+use crate::utils::mirror::{download_url, metadata_url};
+use crate::utils::runner::CommandRunner;
 use crate::utils::system::{get_os, OS};
-use crate::utils::version::compare_versions;
+use crate::utils::uninstall::{Step, Toolchain};
+use crate::utils::version::{Scheme, Version};
+
+pub fn node_layout() -> Layout {
+    Layout {
+        language: "node",
+        scheme: Scheme::Semver,
+        bin_subdir: if get_os() == OS::Windows { "" } else { "bin" },
+        binaries: &["node", "npm", "npx"],
+        version_args: &["--version"],
+    }
+}
 
-pub async fn fetch_latest_node_version() -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
-    let response = reqwest::get("https://nodejs.org/dist/index.json").await?;
-    let body = response.text().await?;
+pub async fn fetch_latest_node_version(fetcher: &dyn Fetcher) -> Result<String> {
+    let body = fetcher.fetch(&metadata_url("node", "index.json")).await?;
 
+    let current = settings::get().config.get("node.channel").as_deref() == Some("current");
     let releases: Vec<Value> = from_str(&body)?;
     if let Some(lts_release) = releases.iter().find(|release| {
-        release["lts"].as_bool().unwrap_or(false) ||
+        current ||
+            release["lts"].as_bool().unwrap_or(false) ||
             release["lts"].as_str().map(|s| !s.is_empty()).unwrap_or(false)
     }) {
         let version = lts_release["version"]
             .as_str()
-            .ok_or("Invalid version format")?
-            .to_string();
-        Ok(version[1..].to_string())
+            .ok_or_else(|| Error::Parse("Invalid version format".to_string()))?;
+        Ok(version.trim_start_matches('v').to_string())
     } else {
-        Err("No LTS version found".into())
+        Err(Error::Parse("No LTS version found".to_string()))
     }
 }
 
-async fn get_installed_node_version() -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
-    match which("node") {
-        Ok(_) => {
-            let output = Command::new("node").arg("-v").output()?;
-            let version_info = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            Ok(version_info[1..].to_string())
-        }
-        Err(_) => Err("Node is not installed".into()),
-    }
+pub async fn list_node_releases(fetcher: &dyn Fetcher) -> Result<Vec<RemoteVersion>> {
+    let body = fetcher.fetch(&metadata_url("node", "index.json")).await?;
+
+    let releases: Vec<Value> = from_str(&body)?;
+    Ok(releases
+        .iter()
+        .filter_map(|release| {
+            let version = release["version"].as_str()?;
+            Some(RemoteVersion {
+                version: version.trim_start_matches('v').to_string(),
+                note: release["lts"].as_str().map(|codename| format!("LTS {}", codename)),
+            })
+        })
+        .collect())
 }
 
-fn uninstall_node() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let os = get_os();
+pub async fn resolve_node_version(fetcher: &dyn Fetcher, requested: &str) -> Result<String> {
+    let requested = requested.trim_start_matches('v');
+    let lts = requested.strip_prefix("lts/").map(str::to_ascii_lowercase);
+    let latest = matches!(requested, "node" | "latest" | "current" | "stable");
+    if lts.is_none() && !latest && Version::parse(Scheme::Semver, requested)?.is_complete() {
+        return Ok(requested.to_string());
+    }
 
-    let potential_paths = match os {
-        OS::Windows => vec![
-            "C:\\Program Files\\nodejs".to_string(),
-            "C:\\nodejs".to_string(),
-            format!("{}\\nodejs", env::var("ProgramFiles").unwrap_or_default()),
-            format!("{}\\Node", env::var("ProgramFiles").unwrap_or_default()),
-        ],
-        OS::Linux => vec![
-            "/usr/local/node".to_string(),
-            "/usr/local/bin/node".to_string(),
-            "/usr/bin/node".to_string(),
-            "/opt/node".to_string(),
-        ],
-        OS::MacOS => vec![
-            "/usr/local/node".to_string(),
-            "/usr/local/bin/node".to_string(),
-            "/opt/node".to_string(),
-            "/opt/local/bin/node".to_string(),
-        ],
-        OS::Unknown => return Err("Unsupported OS for Node uninstallation".into()),
-    };
+    let body = fetcher.fetch(&metadata_url("node", "index.json")).await?;
 
-    let mut node_path_found = false;
+    let releases: Vec<Value> = from_str(&body)?;
+    releases
+        .iter()
+        .filter(|release| match lts.as_deref() {
+            Some("*") => release["lts"].as_str().is_some(),
+            Some(codename) => release["lts"].as_str().is_some_and(|name| name.to_ascii_lowercase() == codename),
+            None => true,
+        })
+        .filter_map(|release| release["version"].as_str())
+        .map(|version| version.trim_start_matches('v'))
+        .filter(|version| lts.is_some() || latest || matches_version_prefix(version, requested))
+        .filter_map(|version| Version::parse(Scheme::Semver, version).ok().map(|parsed| (parsed, version)))
+        .max_by(|a, b| a.0.cmp(&b.0))
+        .map(|(_, version)| version.to_string())
+        .ok_or_else(|| Error::Other(format!("No Node.js release matches {}", requested)))
+}
 
-    match os {
-        OS::Windows => {
-            let uninstall_result = Command::new("wmic")
-                .args(&["product", "where", "name='Node.js'", "call", "uninstall"])
-                .status();
-
-            if let Ok(status) = uninstall_result {
-                if status.success() {
-                    println!("Node.js uninstalled via MSI installer");
-                    node_path_found = true;
-                }
-            }
+pub async fn fetch_node_checksum(fetcher: &dyn Fetcher, version: &str, file_name: &str) -> Result<String> {
+    let url = metadata_url("node", &format!("v{}/SHASUMS256.txt", version));
+    let body = fetcher.fetch(&url).await?;
 
-            if !node_path_found {
-                for path in &potential_paths {
-                    if fs::metadata(path).is_ok() {
-                        println!("Node.js path found: {}", path);
-                        match Command::new("cmd")
-                            .args(&["/C", "rmdir", "/S", "/Q", path])
-                            .status()
-                        {
-                            Ok(status) if status.success() => {
-                                println!("Node.js has been removed from: {}", path);
-                                node_path_found = true;
-                                break;
-                            }
-                            _ => continue,
-                        }
-                    }
-                }
-            }
-        }
-        OS::Linux | OS::MacOS => {
-            for path in &potential_paths {
-                if fs::metadata(path).is_ok() {
-                    println!("Node.js path found: {}", path);
-                    Command::new("sudo")
-                        .args(["rm", "-rf", path])
-                        .status()?;
-                    println!("Node.js has been removed from: {}", path);
-                    node_path_found = true;
-                    break;
-                }
-            }
-        }
-        OS::Unknown => unreachable!(),
+    find_in_shasums(&body, file_name)
+        .ok_or_else(|| Error::Parse(format!("{} is not listed in {}", file_name, url)))
+}
+
+pub async fn get_installed_node_version(runner: &dyn CommandRunner) -> Result<String> {
+    if runner.locate("node").is_none() {
+        return Err(Error::NotInstalled("Node.js".to_string()));
     }
 
-    if let Ok(home) = env::var("HOME").or(env::var("USERPROFILE")) {
-        let npm_path = format!("{}\\AppData\\Roaming\\npm", home);
-        let npm_cache = format!("{}\\AppData\\Roaming\\npm-cache", home);
+    let output = runner.output("node", &["-v"])?;
+    if !output.success {
+        return Err(Error::command_failed("node -v", output.stderr.trim()));
+    }
+    Ok(output.stdout.trim().trim_start_matches('v').to_string())
+}
 
-        if fs::metadata(&npm_path).is_ok() {
-            fs::remove_dir_all(&npm_path)?;
-            println!("Cleaned up global npm modules from: {}", npm_path);
-        }
-        if fs::metadata(&npm_cache).is_ok() {
-            fs::remove_dir_all(&npm_cache)?;
-            println!("Cleaned up npm cache from: {}", npm_cache);
-        }
+pub fn node_toolchain() -> Toolchain {
+    Toolchain {
+        name: "Node.js",
+        binaries: node_layout().binaries,
+        markers: if get_os() == OS::Windows {
+            &["node_modules/npm/package.json"]
+        } else {
+            &["include/node/node_version.h"]
+        },
+        uninstaller: None,
+        conda_package: "nodejs",
+        workspaces: npm_workspaces,
     }
+}
 
-    if !node_path_found {
-        return Err("Node.js installation path not found in any of the checked locations.".into());
+fn npm_workspaces(_runner: &dyn CommandRunner) -> Vec<PathBuf> {
+    let mut workspaces: Vec<PathBuf> = ["NPM_CONFIG_PREFIX", "NPM_CONFIG_CACHE"]
+        .iter()
+        .filter_map(env::var_os)
+        .map(PathBuf::from)
+        .collect();
+    if let Some(appdata) = env::var_os("APPDATA").map(PathBuf::from) {
+        workspaces.push(appdata.join("npm"));
+        workspaces.push(appdata.join("npm-cache"));
     }
+    if let Some(home) = dirs::home_dir() {
+        workspaces.push(home.join(".npm"));
+        workspaces.push(home.join(".npm-global"));
+    }
+    workspaces
+}
 
-    Ok(())
+pub fn nvm_uninstall_steps(version: &str) -> Vec<Step> {
+    let version = format!("v{}", version.trim_start_matches('v'));
+    let mut step = Step::command(format!("node {}", version), &["nvm", "uninstall", &version]);
+    let nvm_dir = env::var_os("NVM_DIR")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".nvm")));
+    if let Some(nvm_dir) = nvm_dir {
+        step = step.freeing(nvm_dir.join("versions").join("node").join(&version));
+    }
+    vec![step]
 }
 
-pub async fn install_node(version: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+pub async fn install_node(version: &str) -> Result<()> {
     let os = get_os();
-
-    let download_url = match os {
-        OS::Windows => format!("https://nodejs.org/dist/v{}/node-v{}-x64.msi", version, version),
-        OS::Linux => format!("https://nodejs.org/dist/v{}/node-v{}-linux-x64.tar.gz", version, version),
-        OS::MacOS => format!("https://nodejs.org/dist/v{}/node-v{}-darwin-x64.tar.gz", version, version),
-        OS::Unknown => return Err("Unsupported OS for Node.js installation".into()),
+    let system_wide = settings::get().system_wide;
+
+    let file_name = match os {
+        OS::Windows if system_wide => format!("node-v{}-x64.msi", version),
+        OS::Windows => format!("node-v{}-win-x64.zip", version),
+        OS::Linux => format!("node-v{}-linux-x64.tar.xz", version),
+        OS::MacOS => format!("node-v{}-darwin-x64.tar.xz", version),
+        OS::Unknown => return Err(Error::UnsupportedOs("Node.js installation".to_string())),
     };
+    let download_url = download_url("node", &format!("v{}/{}", version, file_name));
 
-    println!("Downloading Node.js LTS v{} from {}", version, download_url);
-
-    let response = reqwest::get(&download_url).await?;
-    let bytes = response.bytes().await?;
+    say!("Downloading Node.js v{} from {}", version, download_url);
 
-    let file_name = if os == OS::Windows {
-        "node-installer.msi"
-    } else {
-        "node.tar.gz"
-    };
+    let expected = fetch_node_checksum(&MetadataFetcher, version, &file_name).await?;
+    let archive = download(&download_url, &file_name).await?;
+    let digest = verify_sha256(&archive, &expected)?;
+    record_digest("node", version, &file_name, &digest)?;
 
-    fs::write(file_name, &bytes)?;
+    say!("Installing Node.js...");
 
-    println!("Installing Node.js...");
+    if !system_wide {
+        let result = install_into_root(&node_layout(), version, &archive);
+        remove_path(&archive)?;
+        result?;
+        say!("Node.js v{} installed successfully!", version);
+        return Ok(());
+    }
 
     match os {
         OS::Windows => {
-            let status = Command::new("msiexec")
-                .args(["/i", file_name, "/quiet", "/norestart"])
-                .status()?;
+            let status = run(Command::new("msiexec")
+                .arg("/i")
+                .arg(&archive)
+                .args(["/quiet", "/norestart"]))?;
             if !status.success() {
-                return Err("Failed to install Node.js MSI".into());
+                return Err(Error::command_failed("msiexec", format!("exited with {}", status)));
             }
         }
         OS::Linux | OS::MacOS => {
-            let status = Command::new("sudo")
-                .args(["tar", "-C", "/usr/local", "-xzf", file_name])
-                .status()?;
-            if !status.success() {
-                return Err("Failed to extract Node.js archive".into());
-            }
-
             let extracted_dir = format!(
                 "/usr/local/node-v{}-{}",
                 version,
                 if os == OS::Linux { "linux-x64" } else { "darwin-x64" }
             );
-            Command::new("sudo")
-                .args(["ln", "-sf", &format!("{}/bin/node", extracted_dir), "/usr/local/bin/node"])
-                .status()?;
-            Command::new("sudo")
-                .args(["ln", "-sf", &format!("{}/bin/npm", extracted_dir), "/usr/local/bin/npm"])
-                .status()?;
+            install_system_wide(&node_layout(), version, &archive, Path::new(&extracted_dir))?;
+            run(Command::new("sudo")
+                .args(["ln", "-sf", &format!("{}/bin/node", extracted_dir), "/usr/local/bin/node"]))?;
+            run(Command::new("sudo")
+                .args(["ln", "-sf", &format!("{}/bin/npm", extracted_dir), "/usr/local/bin/npm"]))?;
         }
         OS::Unknown => {
-            return Err("Unsupported OS for Node.js installation".into());
+            return Err(Error::UnsupportedOs("Node.js installation".to_string()));
         }
     }
 
-    fs::remove_file(file_name)?;
+    remove_path(&archive)?;
 
-    println!("Node.js v{} installed successfully!", version);
+    say!("Node.js v{} installed successfully!", version);
     Ok(())
 }
 
-pub async fn update_node() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let installed_version = get_installed_node_version().await.unwrap_or("None".to_string());
-    let latest_version = fetch_latest_node_version().await?;
-
-    if installed_version == "None" {
-        println!("Node.js is not installed. Installing latest LTS version...");
-        install_node(&latest_version).await?;
-    } else {
-        match compare_versions(&installed_version, &latest_version) {
-            std::cmp::Ordering::Equal => {
-                println!("Node.js is already up to date (v{})", installed_version);
-            }
-            std::cmp::Ordering::Less => {
-                println!("Updating Node.js from v{} to v{}", installed_version, latest_version);
-                uninstall_node()?;
-                install_node(&latest_version).await?;
-            }
-            std::cmp::Ordering::Greater => {
-                println!("Installed Node.js (v{}) is newer than latest LTS (v{})",
-                         installed_version, latest_version);
-            }
-        }
+pub fn use_nvm() -> bool {
+    match settings::get().config.get("node.provider").as_deref() {
+        Some("nvm") => true,
+        Some("tarball") => false,
+        _ => is_nvm_installed(),
     }
-
-    Ok(())
 }
 
 pub fn is_nvm_installed() -> bool {
     let home = env::var("HOME").or(env::var("USERPROFILE")).unwrap_or_default();
-    let nvm_paths = vec![
-        format!("{}/.nvm/nvm.sh", home),
-        format!("{}\\AppData\\Roaming\\nvm", home),
-    ];
+    let nvm_paths = [format!("{}/.nvm/nvm.sh", home),
+        format!("{}\\AppData\\Roaming\\nvm", home)];
 
     nvm_paths.iter().any(|path| Path::new(path).exists()) ||
         Command::new("nvm").arg("version").output().is_ok()
 }
 
-pub async fn update_with_nvm() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    println!("Updating Node.js using NVM...");
-
-    let latest_version = fetch_latest_node_version().await?;
+pub async fn update_with_nvm(version: &str) -> Result<()> {
+    say!("Updating Node.js using NVM...");
 
-    let status = Command::new("nvm")
-        .args(&["install", &latest_version])
-        .status()?;
+    let status = run(Command::new("nvm").args(["install", version]))?;
 
     if !status.success() {
-        return Err("Failed to install Node.js with NVM".into());
+        return Err(Error::command_failed("nvm install", format!("exited with {}", status)));
     }
 
-    let status = Command::new("nvm")
-        .args(&["use", &latest_version]).status()?;
+    let status = run(Command::new("nvm").args(["use", version]))?;
 
     if !status.success() {
-        return Err("Failed to switch to new Node.js version with NVM".into());
+        return Err(Error::command_failed("nvm use", format!("exited with {}", status)));
     }
 
-    println!("Node.js updated to v{} using NVM", latest_version);
+    say!("Node.js updated to v{} using NVM", version);
     Ok(())
 }
 
-pub async fn install_with_nvm() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    println!("Installing Node.js using NVM...");
+pub async fn install_with_nvm(version: &str) -> Result<()> {
+    say!("Installing Node.js using NVM...");
 
-    let latest_version = fetch_latest_node_version().await?;
-
-    let status = Command::new("nvm")
-        .args(&["install", &latest_version]).status()?;
+    let status = run(Command::new("nvm").args(["install", version]))?;
 
     if !status.success() {
-        return Err("Failed to install Node.js with NVM".into());
+        return Err(Error::command_failed("nvm install", format!("exited with {}", status)));
     }
 
-    println!("Node.js v{} installed using NVM", latest_version);
+    say!("Node.js v{} installed using NVM", version);
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::fetcher::FixtureFetcher;
+
+    const INDEX: &str = include_str!("../../tests/fixtures/node/index.json");
+    const SHASUMS: &str = include_str!("../../tests/fixtures/node/SHASUMS256-v22.12.0.txt");
+
+    fn fixtures(index: &str) -> FixtureFetcher {
+        FixtureFetcher::new()
+            .with(metadata_url("node", "index.json"), index)
+            .with(metadata_url("node", "v22.12.0/SHASUMS256.txt"), SHASUMS)
+    }
+
+    #[tokio::test]
+    async fn latest_is_the_newest_lts_release() {
+        assert_eq!(fetch_latest_node_version(&fixtures(INDEX)).await.unwrap(), "22.12.0");
+    }
+
+    #[tokio::test]
+    async fn aliases_and_partial_versions_resolve() {
+        let fetcher = fixtures(INDEX);
+        assert_eq!(resolve_node_version(&fetcher, "20").await.unwrap(), "20.18.1");
+        assert_eq!(resolve_node_version(&fetcher, "v22.11").await.unwrap(), "22.11.0");
+        assert_eq!(resolve_node_version(&fetcher, "lts/iron").await.unwrap(), "20.18.1");
+        assert_eq!(resolve_node_version(&fetcher, "lts/*").await.unwrap(), "22.12.0");
+        assert_eq!(resolve_node_version(&fetcher, "latest").await.unwrap(), "23.4.0");
+        assert_eq!(resolve_node_version(&fetcher, "18.19.1").await.unwrap(), "18.19.1");
+        assert!(matches!(resolve_node_version(&fetcher, "lts/gallium").await, Err(Error::Other(_))));
+    }
+
+    #[tokio::test]
+    async fn remote_list_names_lts_lines() {
+        let releases = list_node_releases(&fixtures(INDEX)).await.unwrap();
+        assert_eq!(releases.len(), 7);
+        assert_eq!(releases[0].version, "23.4.0");
+        assert_eq!(releases[0].note, None);
+        assert_eq!(releases[1].note.as_deref(), Some("LTS Jod"));
+        assert_eq!(releases[6].note.as_deref(), Some("LTS Hydrogen"));
+    }
+
+    #[tokio::test]
+    async fn checksum_comes_from_shasums() {
+        let fetcher = fixtures(INDEX);
+        assert_eq!(
+            fetch_node_checksum(&fetcher, "22.12.0", "node-v22.12.0-linux-x64.tar.xz").await.unwrap(),
+            "bd0fd9cf17d24dc8025f8d9c54d5067ca2962c3ad3b70133490142b99fde538a"
+        );
+        assert!(matches!(
+            fetch_node_checksum(&fetcher, "22.12.0", "node-v22.12.0-aix-ppc64.tar.gz").await,
+            Err(Error::Parse(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn empty_release_lists_are_reported() {
+        let fetcher = fixtures("[]");
+        assert!(matches!(fetch_latest_node_version(&fetcher).await, Err(Error::Parse(_))));
+        assert!(matches!(resolve_node_version(&fetcher, "lts/*").await, Err(Error::Other(_))));
+        assert!(list_node_releases(&fetcher).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn malformed_payloads_are_parse_errors() {
+        for body in ["Not Found", r#"{"version":"v22.12.0"}"#, r#"[{"version":22,"lts":"Jod"}]"#] {
+            assert!(
+                matches!(fetch_latest_node_version(&fixtures(body)).await, Err(Error::Parse(_))),
+                "accepted {}",
+                body
+            );
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! Progress messages, confirmation prompts and structured (JSON or YAML) output.
+
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use serde::Serialize;
+use crate::error::{Error, Result};
+use crate::settings;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    Yaml,
+}
+
+impl OutputFormat {
+    pub fn parse(value: &str) -> Option<OutputFormat> {
+        match value {
+            "text" => Some(OutputFormat::Text),
+            "json" => Some(OutputFormat::Json),
+            "yaml" => Some(OutputFormat::Yaml),
+            _ => None,
+        }
+    }
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! say {
+    ($($arg:tt)*) => {
+        $crate::output::write_message(format_args!($($arg)*))
+    };
+}
+
+/// Formats a progress message like `println!` and passes it to [`write_message`].
+#[doc(inline)]
+pub use say;
+
+pub fn write_message(message: fmt::Arguments) {
+    if settings::get().quiet {
+        return;
+    }
+    if is_structured() {
+        eprintln!("{}", message);
+    } else {
+        println!("{}", message);
+    }
+}
+
+pub fn warn(message: impl fmt::Display) {
+    if !settings::get().quiet {
+        eprintln!("{}", message);
+    }
+}
+
+pub fn is_structured() -> bool {
+    settings::get().format != OutputFormat::Text
+}
+
+pub fn emit<T: Serialize>(value: &T) -> Result<()> {
+    match settings::get().format {
+        OutputFormat::Text => {}
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(value)?),
+        OutputFormat::Yaml => print!(
+            "{}",
+            serde_norway::to_string(value).map_err(|e| Error::Other(e.to_string()))?
+        ),
+    }
+    Ok(())
+}
+
+pub fn confirm(prompt: &str) -> Result<bool> {
+    eprint!("{} [y/N] ", prompt);
+    io::stderr().flush()?;
+
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes"))
+}
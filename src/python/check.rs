@@ -1,79 +1,95 @@
-﻿use std::process::Command;
-use std::error::Error;
-use crate::utils::system::{get_os, OS};
+use crate::error::{Error, Result};
+use crate::output::say;
+use crate::utils::runner::CommandRunner;
 
-pub async fn check_python_version() -> Result<(), Box<dyn Error + Send + Sync>> {
-    let mut found_any = false;
-    let os = get_os();
+pub async fn check_python_version(runner: &dyn CommandRunner) -> Result<Vec<String>> {
+    let mut found = Vec::new();
 
-    fn run_command(cmd: &str, args: &[&str]) -> Option<String> {
-        Command::new(cmd)
-            .args(args)
-            .output()
+    let run_command = |cmd: &str, args: &[&str]| -> Option<String> {
+        runner
+            .output(cmd, args)
             .ok()
-            .and_then(|output| {
-                if output.status.success() {
-                    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
-                } else {
-                    None
-                }
-            })
-    }
+            .filter(|output| output.success)
+            .map(|output| output.text().to_string())
+            .filter(|text| !text.is_empty())
+    };
 
-    if let Some(version) = run_command("python", &["--version"]) {
-        found_any = true;
-        println!("🐍 System Python: {}", version);
-        if os == OS::Windows {
-            if let Some(path) = run_command("where", &["python"]) {
-                println!("   📍 Path: {}", path);
+    let installations = [
+        ("python", "🐍 System Python", "python"),
+        ("python3", "🐍 Python3 (Alternative)", "python3"),
+        ("conda", "📦 Anaconda detected", "python"),
+    ];
+    for (program, label, pip_python) in installations {
+        if let Some(version) = run_command(program, &["--version"]) {
+            say!("{}: {}", label, version);
+            if let Some(path) = runner.locate(program) {
+                say!("   📍 Path: {}", path.display());
             }
-        } else {
-            if let Some(path) = run_command("which", &["python"]) {
-                println!("   📍 Path: {}", path);
+            if let Some(pip) = run_command(pip_python, &["-m", "pip", "--version"]) {
+                say!("   📦 Pip installed: {}", pip);
             }
-        }
-        if let Some(pip) = run_command("python", &["-m", "pip", "--version"]) {
-            println!("   📦 Pip installed: {}", pip);
+            found.push(version);
         }
     }
 
-    if let Some(version) = run_command("python3", &["--version"]) {
-        found_any = true;
-        println!("🐍 Python3 (Alternative): {}", version);
-        if os == OS::Windows {
-            if let Some(path) = run_command("where", &["python3"]) {
-                println!("   📍 Path: {}", path);
-            }
-        } else {
-            if let Some(path) = run_command("which", &["python3"]) {
-                println!("   📍 Path: {}", path);
-            }
-        }
-        if let Some(pip) = run_command("python3", &["-m", "pip", "--version"]) {
-            println!("   📦 Pip installed: {}", pip);
-        }
+    if found.is_empty() {
+        say!("❌ No Python installation found.");
+        return Err(Error::NotInstalled("Python".to_string()));
     }
 
-    if let Some(conda_version) = run_command("conda", &["--version"]) {
-        found_any = true;
-        println!("📦 Anaconda detected: {}", conda_version);
-        if os == OS::Windows {
-            if let Some(path) = run_command("where", &["conda"]) {
-                println!("   📍 Path: {}", path);
-            }
-        } else {
-            if let Some(path) = run_command("which", &["conda"]) {
-                println!("   📍 Path: {}", path);
-            }
-        }
-        if let Some(pip) = run_command("python", &["-m", "pip", "--version"]) {
-            println!("   📦 Pip installed: {}", pip);
-        }
+    Ok(found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::python::update::get_installed_python_version;
+    use crate::utils::runner::ScriptedRunner;
+
+    const CONDA: &str = include_str!("../../tests/fixtures/python/conda-version.txt");
+    const CONDA_LEGACY: &str = include_str!("../../tests/fixtures/python/conda-version-legacy-stderr.txt");
+    const PIP: &str = include_str!("../../tests/fixtures/python/pip-version-conda.txt");
+    const PYTHON_2: &str = include_str!("../../tests/fixtures/python/python2-version-stderr.txt");
+    const WINDOWS: &str = include_str!("../../tests/fixtures/python/python-version-windows.txt");
+
+    #[tokio::test]
+    async fn conda_banners_are_detected() {
+        let runner = ScriptedRunner::new()
+            .stdout("conda --version", CONDA)
+            .stdout("python -m pip --version", PIP);
+        assert_eq!(check_python_version(&runner).await.unwrap(), ["conda 23.7.4"]);
+
+        let legacy = ScriptedRunner::new().stderr("conda --version", CONDA_LEGACY);
+        assert_eq!(check_python_version(&legacy).await.unwrap(), ["conda 4.3.30"]);
     }
 
-    if !found_any {
-        println!("❌ No Python installation found.");
+    #[tokio::test]
+    async fn every_interpreter_is_listed() {
+        let runner = ScriptedRunner::new()
+            .stderr("python --version", PYTHON_2)
+            .stdout("python3 --version", WINDOWS)
+            .stdout("conda --version", CONDA);
+        assert_eq!(
+            check_python_version(&runner).await.unwrap(),
+            ["Python 2.7.18", "Python 3.12.1", "conda 23.7.4"]
+        );
     }
 
-    Ok(())
+    #[tokio::test]
+    async fn installed_version_prefers_python3() {
+        let runner = ScriptedRunner::new()
+            .stderr("python --version", PYTHON_2)
+            .stdout("python3 --version", WINDOWS);
+        assert_eq!(get_installed_python_version(&runner).await.unwrap(), "3.12.1");
+
+        let python2 = ScriptedRunner::new().stderr("python --version", PYTHON_2);
+        assert_eq!(get_installed_python_version(&python2).await.unwrap(), "2.7.18");
+    }
+
+    #[tokio::test]
+    async fn missing_python_is_reported() {
+        assert!(matches!(check_python_version(&ScriptedRunner::new()).await, Err(Error::NotInstalled(_))));
+        let broken = ScriptedRunner::new().failing("python3 --version", "python3: can't open file");
+        assert!(matches!(get_installed_python_version(&broken).await, Err(Error::NotInstalled(_))));
+    }
 }
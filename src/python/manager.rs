@@ -0,0 +1,145 @@
+use std::path::PathBuf;
+use async_trait::async_trait;
+use which::which;
+use crate::error::{Error, Result};
+use crate::manager::{
+    find_installation, update_language, Installation, InstalledVersion, LanguageManager, RemoteVersion,
+};
+use crate::output::say;
+use crate::python::check::check_python_version;
+use crate::settings;
+use crate::python::update::{
+    conda_python, fetch_latest_python_version, get_installed_python_version, install_pip, install_pyenv_version,
+    install_python, list_pyenv_versions, list_python_releases, pyenv_global_version, pyenv_root, pyenv_uninstall_steps, python_provider,
+    python_toolchain, set_pyenv_global, update_pip, update_python,
+};
+use crate::utils::fetcher::MetadataFetcher;
+use crate::utils::provider::{detect_provider, Provider};
+use crate::utils::resolve::requested_version;
+use crate::utils::runner::SystemRunner;
+use crate::utils::shims::{print_path_hint, write_shims};
+use crate::utils::uninstall::{toolchain_steps, Step};
+use crate::utils::version::Scheme;
+
+pub struct PythonManager;
+
+#[async_trait]
+impl LanguageManager for PythonManager {
+    fn name(&self) -> &'static str {
+        "python"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Python"
+    }
+
+    fn version_scheme(&self) -> Scheme {
+        Scheme::Pep440
+    }
+
+    fn shim_binaries(&self) -> &'static [&'static str] {
+        &["python3", "pip3", "python", "pip"]
+    }
+
+    fn version_files(&self) -> &'static [&'static str] {
+        &[".python-version"]
+    }
+
+    fn global_version(&self) -> Option<String> {
+        pyenv_global_version()
+    }
+
+    fn version_bin_dir(&self, version: &str) -> Result<PathBuf> {
+        let dir = pyenv_root().join("versions").join(version);
+        if !dir.is_dir() {
+            return Err(Error::NotInstalled(format!("Python {}", version)));
+        }
+        Ok(dir.join("bin"))
+    }
+
+    async fn check(&self) -> Result<()> {
+        check_python_version(&SystemRunner).await.map(|_| ())
+    }
+
+    async fn detect_installed(&self) -> Result<Installation> {
+        let path = which("python3")
+            .or_else(|_| which("python"))
+            .map_err(|_| Error::NotInstalled("Python".to_string()))?;
+        let version = get_installed_python_version(&SystemRunner).await?;
+        Ok(Installation { version, path })
+    }
+
+    async fn fetch_latest(&self) -> Result<String> {
+        fetch_latest_python_version(&MetadataFetcher).await
+    }
+
+    async fn install(&self, version: Option<&str>) -> Result<()> {
+        let provider = python_provider();
+        match (provider.as_str(), version) {
+            ("pyenv", Some(version)) => install_pyenv_version(version).await,
+            ("pyenv", None) => install_pyenv_version(&fetch_latest_python_version(&MetadataFetcher).await?).await,
+            ("conda", _) => conda_python("install", version).await,
+            ("auto", Some(version)) if which("pyenv").is_ok() => install_pyenv_version(version).await,
+            (_, Some(version)) => {
+                say!("Python {} can only be installed with pyenv or conda (python.provider).", version);
+                Err(Error::NotInstalled("pyenv".to_string()))
+            }
+            _ => {
+                install_python(provider == "auto").await?;
+                say!("Installing pip...");
+                install_pip().await
+            }
+        }
+    }
+
+    async fn list_remote(&self) -> Result<Vec<RemoteVersion>> {
+        list_python_releases(&MetadataFetcher).await
+    }
+
+    async fn uninstall_plan(&self, version: Option<&str>) -> Result<Vec<Step>> {
+        if let Some(version) = version
+            && which("pyenv").is_ok()
+        {
+            return pyenv_uninstall_steps(version);
+        }
+
+        let installation = find_installation(self, version).await?;
+        match detect_provider(&SystemRunner, &installation.path) {
+            Provider::Pyenv => pyenv_uninstall_steps(&installation.version),
+            provider if !settings::get().system_wide => Err(Error::Usage(format!(
+                "The Python at {} was installed by {}; pass --system to remove it",
+                installation.path.display(),
+                provider
+            ))),
+            _ => toolchain_steps(&SystemRunner, &python_toolchain(), &installation),
+        }
+    }
+
+    async fn list_installed(&self) -> Result<Vec<InstalledVersion>> {
+        list_pyenv_versions(&SystemRunner).await
+    }
+
+    async fn use_version(&self, version: &str) -> Result<()> {
+        set_pyenv_global(version).await?;
+        write_shims(self.shim_binaries())?;
+        print_path_hint();
+        Ok(())
+    }
+
+    async fn update(&self) -> Result<()> {
+        if let Some(requested) = requested_version(self) {
+            say!("Python {} requested by {}", requested.version, requested.source);
+            return self.install(Some(&requested.version)).await;
+        }
+
+        match python_provider().as_str() {
+            "pyenv" => update_language(self).await,
+            "conda" => conda_python("update", None).await,
+            provider => {
+                update_python(provider == "auto").await?;
+                say!("Updating pip...");
+                update_pip().await
+            }
+        }
+    }
+}
@@ -1,3 +1,3 @@
-﻿pub(crate) mod update;
-pub(crate) mod cli;
-pub(crate) mod check;
\ No newline at end of file
+pub(crate) mod check;
+pub(crate) mod update;
+pub(crate) mod manager;
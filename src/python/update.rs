@@ -1,131 +1,204 @@
-﻿use std::process::Command;
-use std::error::Error;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use serde_json::Value;
+use crate::error::{Error, Result};
+use crate::manager::{InstalledVersion, RemoteVersion};
+use crate::output::say;
+use crate::settings;
+use crate::utils::command::run;
+use crate::utils::fetcher::Fetcher;
+use crate::utils::mirror::metadata_url;
+use crate::utils::runner::{CommandRunner, SystemRunner};
 use crate::utils::system::{get_os, OS};
-pub async fn update_python() -> Result<(), Box<dyn Error + Send + Sync>> {
+use crate::utils::uninstall::{Step, Toolchain};
+use crate::utils::version::{Scheme, Version};
+
+pub async fn fetch_latest_python_version(fetcher: &dyn Fetcher) -> Result<String> {
+    let releases = fetch_python_releases(fetcher).await?;
+    if let Some(latest_release) = releases.iter().find(|release| {
+        release["is_latest"].as_bool().unwrap_or(false)
+    }) {
+        let name = latest_release["name"]
+            .as_str()
+            .ok_or_else(|| Error::Parse("Invalid version format".to_string()))?;
+        Ok(name.trim_start_matches("Python").trim().to_string())
+    } else {
+        Err(Error::Parse("No latest Python release found".to_string()))
+    }
+}
+
+pub async fn list_python_releases(fetcher: &dyn Fetcher) -> Result<Vec<RemoteVersion>> {
+    let mut releases: Vec<(Version, RemoteVersion)> = fetch_python_releases(fetcher)
+        .await?
+        .iter()
+        .filter_map(|release| {
+            let version = release["name"].as_str()?.trim_start_matches("Python").trim();
+            let parsed = Version::parse(Scheme::Pep440, version).ok()?;
+            let latest = release["is_latest"].as_bool().unwrap_or(false);
+            Some((
+                parsed,
+                RemoteVersion {
+                    version: version.to_string(),
+                    note: latest.then(|| "latest".to_string()),
+                },
+            ))
+        })
+        .collect();
+    releases.sort_by(|a, b| b.0.cmp(&a.0));
+    Ok(releases.into_iter().map(|(_, release)| release).collect())
+}
+
+async fn fetch_python_releases(fetcher: &dyn Fetcher) -> Result<Vec<Value>> {
+    let body = fetcher.fetch(&metadata_url(
+        "python",
+        "api/v2/downloads/release/?is_published=true&pre_release=false&version=3",
+    ))
+    .await?;
+    Ok(serde_json::from_str(&body)?)
+}
+
+pub async fn get_installed_python_version(runner: &dyn CommandRunner) -> Result<String> {
+    for cmd in ["python3", "python"] {
+        if let Ok(output) = runner.output(cmd, &["--version"])
+            && output.success
+            && let Some(version) = output.text().split_whitespace().nth(1)
+        {
+            return Ok(version.to_string());
+        }
+    }
+
+    Err(Error::NotInstalled("Python".to_string()))
+}
+
+pub async fn update_python(allow_conda: bool) -> Result<()> {
     let os = get_os();
 
     match os {
         OS::Windows => {
-            if let Ok(status) = Command::new("conda")
-                .arg("update")
-                .arg("python")
-                .arg("-y")
-                .status()
+            if allow_conda
+                && let Ok(status) = run(Command::new("conda").arg("update").arg("python").arg("-y"))
+                && status.success()
             {
-                if status.success() {
-                    println!("Python successfully updated via Anaconda.");
-                    return Ok(());
-                }
+                say!("Python successfully updated via Anaconda.");
+                return Ok(());
             }
 
-            let status = Command::new("winget")
+            let status = run(Command::new("winget")
                 .arg("install")
                 .arg("--id")
                 .arg("Python.Python.3")
                 .arg("--source")
-                .arg("winget")
-                .status()?;
+                .arg("winget"))?;
 
             if status.success() {
-                println!("Python updated via winget.");
+                say!("Python updated via winget.");
                 return Ok(());
             }
         }
         OS::Linux => {
-            let status = Command::new("sudo")
-                .arg("apt")
-                .arg("update")
-                .arg("python3")
-                .status()?;
+            let status = run(Command::new("sudo").arg("apt").arg("update").arg("python3"))?;
 
             if status.success() {
-                println!("Python updated on Linux.");
+                say!("Python updated on Linux.");
                 return Ok(());
             }
         }
         OS::MacOS => {
-            let status = Command::new("brew")
-                .arg("upgrade")
-                .arg("python")
-                .status()?;
+            let status = run(Command::new("brew").arg("upgrade").arg("python"))?;
 
             if status.success() {
-                println!("Python updated on macOS.");
+                say!("Python updated on macOS.");
                 return Ok(());
             }
         }
         OS::Unknown => {
-            println!("Unknown OS. Python update is not supported.");
+            return Err(Error::UnsupportedOs("Python update".to_string()));
         }
     }
 
-    Err("Python update failed.".into())
+    Err(Error::command_failed("python update", "package manager did not succeed"))
 }
 
-pub async fn install_python() -> Result<(), Box<dyn Error + Send + Sync>> {
+pub async fn install_python(allow_conda: bool) -> Result<()> {
     let os = get_os();
 
     match os {
         OS::Windows => {
-            if let Ok(status) = Command::new("conda")
-                .arg("install")
-                .arg("python")
-                .arg("-y")
-                .status()
+            if allow_conda
+                && let Ok(status) = run(Command::new("conda").arg("install").arg("python").arg("-y"))
+                && status.success()
             {
-                if status.success() {
-                    println!("Python successfully installed via Anaconda.");
-                    return Ok(());
-                }
+                say!("Python successfully installed via Anaconda.");
+                return Ok(());
             }
 
-            let status = Command::new("winget")
+            let status = run(Command::new("winget")
                 .arg("install")
                 .arg("--id")
                 .arg("Python.Python.3")
                 .arg("--source")
-                .arg("winget")
-                .status()?;
+                .arg("winget"))?;
 
             if status.success() {
-                println!("Python installed via winget.");
+                say!("Python installed via winget.");
                 return Ok(());
             }
         }
         OS::Linux => {
-            let status = Command::new("sudo")
+            let status = run(Command::new("sudo")
                 .arg("apt")
                 .arg("install")
                 .arg("python3")
-                .arg("-y")
-                .status()?;
+                .arg("-y"))?;
 
             if status.success() {
-                println!("Python installed on Linux.");
+                say!("Python installed on Linux.");
                 return Ok(());
             }
         }
         OS::MacOS => {
-            let status = Command::new("brew")
-                .arg("install")
-                .arg("python")
-                .status()?;
+            let status = run(Command::new("brew").arg("install").arg("python"))?;
 
             if status.success() {
-                println!("Python installed on macOS.");
+                say!("Python installed on macOS.");
                 return Ok(());
             }
         }
         OS::Unknown => {
-            println!("Unknown OS. Python installation is not supported.");
+            return Err(Error::UnsupportedOs("Python installation".to_string()));
         }
     }
 
-    Err("Python installation failed.".into())
+    Err(Error::command_failed("python install", "package manager did not succeed"))
+}
+
+pub async fn conda_python(action: &str, version: Option<&str>) -> Result<()> {
+    let package = match version {
+        Some(version) => format!("python={}", version),
+        None => "python".to_string(),
+    };
+    let status = run(Command::new("conda").args([action, package.as_str(), "-y"]))
+        .map_err(|_| Error::NotInstalled("conda".to_string()))?;
+
+    if status.success() {
+        say!("Python successfully {} via conda.", if action == "update" { "updated" } else { "installed" });
+        Ok(())
+    } else {
+        Err(Error::command_failed(
+            format!("conda {} {}", action, package),
+            format!("exited with {}", status),
+        ))
+    }
+}
+
+pub fn python_provider() -> String {
+    settings::get().config.get("python.provider").unwrap_or_else(|| "auto".to_string())
 }
 
-pub async fn update_pip() -> Result<(), Box<dyn Error + Send + Sync>> {
-    let mut pip_update_cmd: Command = if is_conda_available() {
+pub async fn update_pip() -> Result<()> {
+    let mut pip_update_cmd: Command = if is_conda_available(&SystemRunner) {
         let mut cmd = Command::new("conda");
         cmd.arg("run")
             .arg("-n")
@@ -147,18 +220,18 @@ pub async fn update_pip() -> Result<(), Box<dyn Error + Send + Sync>> {
         cmd
     };
 
-    let status = pip_update_cmd.status()?;
+    let status = run(&mut pip_update_cmd)?;
 
     if status.success() {
-        println!("pip successfully updated.");
+        say!("pip successfully updated.");
         return Ok(());
     }
 
-    Err("pip update failed.".into())
+    Err(Error::command_failed("pip install --upgrade pip", format!("exited with {}", status)))
 }
 
-pub async fn install_pip() -> Result<(), Box<dyn Error + Send + Sync>> {
-    let mut pip_install_cmd: Command = if is_conda_available() {
+pub async fn install_pip() -> Result<()> {
+    let mut pip_install_cmd: Command = if is_conda_available(&SystemRunner) {
         let mut cmd = Command::new("conda");
         cmd.arg("run")
             .arg("-n")
@@ -176,19 +249,169 @@ pub async fn install_pip() -> Result<(), Box<dyn Error + Send + Sync>> {
         cmd
     };
 
-    let status = pip_install_cmd.status()?;
+    let status = run(&mut pip_install_cmd)?;
 
     if status.success() {
-        println!("pip successfully installed.");
+        say!("pip successfully installed.");
         return Ok(());
     }
 
-    Err("pip installation failed.".into())
+    Err(Error::command_failed("python -m ensurepip", format!("exited with {}", status)))
 }
 
-fn is_conda_available() -> bool {
-    Command::new("conda")
-        .arg("--version")
-        .output()
-        .is_ok()
+pub async fn list_pyenv_versions(runner: &dyn CommandRunner) -> Result<Vec<InstalledVersion>> {
+    let output = runner
+        .output("pyenv", &["versions", "--bare"])
+        .map_err(|_| Error::NotInstalled("pyenv".to_string()))?;
+    if !output.success {
+        return Err(Error::command_failed("pyenv versions", output.stderr.trim()));
+    }
+
+    let global = runner
+        .output("pyenv", &["global"])
+        .map(|output| output.stdout.trim().to_string())
+        .unwrap_or_default();
+
+    Ok(output
+        .stdout
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|version| InstalledVersion {
+            version: version.to_string(),
+            default: global.lines().any(|g| g == version),
+        })
+        .collect())
+}
+
+pub async fn install_pyenv_version(version: &str) -> Result<()> {
+    let status = run(Command::new("pyenv").args(["install", "--skip-existing", version]))
+        .map_err(|_| Error::NotInstalled("pyenv".to_string()))?;
+
+    if status.success() {
+        say!("Python {} installed via pyenv.", version);
+        Ok(())
+    } else {
+        Err(Error::command_failed(
+            format!("pyenv install {}", version),
+            format!("exited with {}", status),
+        ))
+    }
+}
+
+pub async fn set_pyenv_global(version: &str) -> Result<()> {
+    let status = run(Command::new("pyenv").args(["global", version]))
+        .map_err(|_| Error::NotInstalled("pyenv".to_string()))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Error::command_failed(
+            format!("pyenv global {}", version),
+            format!("exited with {}", status),
+        ))
+    }
+}
+
+pub fn pyenv_uninstall_steps(version: &str) -> Result<Vec<Step>> {
+    let dir = pyenv_root().join("versions").join(version);
+    if !dir.is_dir() {
+        return Err(Error::NotInstalled(format!("Python {}", version)));
+    }
+    Ok(vec![
+        Step::command(format!("python {}", version), &["pyenv", "uninstall", "-f", version]).freeing(dir),
+    ])
+}
+
+pub fn python_toolchain() -> Toolchain {
+    Toolchain {
+        name: "Python",
+        binaries: &["python3", "pip3", "python", "pip"],
+        markers: &[],
+        uninstaller: None,
+        conda_package: "python",
+        workspaces: |_| Vec::new(),
+    }
+}
+
+pub fn pyenv_root() -> PathBuf {
+    env::var_os("PYENV_ROOT")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".pyenv")))
+        .unwrap_or_else(|| PathBuf::from(".pyenv"))
+}
+
+pub fn pyenv_global_version() -> Option<String> {
+    fs::read_to_string(pyenv_root().join("version"))
+        .ok()?
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && *line != "system")
+        .map(str::to_string)
+}
+
+fn is_conda_available(runner: &dyn CommandRunner) -> bool {
+    runner.output("conda", &["--version"]).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::fetcher::FixtureFetcher;
+    use crate::utils::runner::ScriptedRunner;
+
+    const RELEASES: &str = include_str!("../../tests/fixtures/python/releases.json");
+    const PYENV_VERSIONS: &str = include_str!("../../tests/fixtures/python/pyenv-versions.txt");
+
+    fn fixtures(releases: &str) -> FixtureFetcher {
+        FixtureFetcher::new().with(
+            metadata_url("python", "api/v2/downloads/release/?is_published=true&pre_release=false&version=3"),
+            releases,
+        )
+    }
+
+    #[tokio::test]
+    async fn latest_is_the_release_marked_latest() {
+        assert_eq!(fetch_latest_python_version(&fixtures(RELEASES)).await.unwrap(), "3.13.1");
+    }
+
+    #[tokio::test]
+    async fn remote_list_is_sorted_newest_first() {
+        let releases = list_python_releases(&fixtures(RELEASES)).await.unwrap();
+        let versions: Vec<&str> = releases.iter().map(|release| release.version.as_str()).collect();
+        assert_eq!(versions, ["3.13.1", "3.13.0", "3.12.10", "3.12.8", "3.11.11", "3.9.21"]);
+        assert_eq!(releases[0].note.as_deref(), Some("latest"));
+        assert!(releases[1..].iter().all(|release| release.note.is_none()));
+    }
+
+    #[tokio::test]
+    async fn empty_release_lists_are_reported() {
+        assert!(matches!(fetch_latest_python_version(&fixtures("[]")).await, Err(Error::Parse(_))));
+        assert!(list_python_releases(&fixtures("[]")).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn malformed_payloads_are_parse_errors() {
+        for body in ["<html></html>", r#"{"name":"Python 3.13.1"}"#, r#"[{"name":3.13,"is_latest":true}]"#] {
+            assert!(
+                matches!(fetch_latest_python_version(&fixtures(body)).await, Err(Error::Parse(_))),
+                "accepted {}",
+                body
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn pyenv_versions_mark_the_global_one() {
+        let runner = ScriptedRunner::new()
+            .stdout("pyenv versions --bare", PYENV_VERSIONS)
+            .stdout("pyenv global", "3.12.7\n");
+        let versions = list_pyenv_versions(&runner).await.unwrap();
+        let defaults: Vec<(&str, bool)> =
+            versions.iter().map(|installed| (installed.version.as_str(), installed.default)).collect();
+        assert_eq!(defaults, [("3.11.10", false), ("3.12.7", true), ("3.13.0", false)]);
+
+        let failing = ScriptedRunner::new().failing("pyenv versions --bare", "pyenv: not initialised");
+        assert!(matches!(list_pyenv_versions(&failing).await, Err(Error::CommandFailed { .. })));
+    }
 }
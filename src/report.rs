@@ -0,0 +1,131 @@
+//! Structured results of checks, installs, updates and status queries.
+
+use std::path::PathBuf;
+use serde::Serialize;
+use crate::error::{Error, Outcome};
+use crate::manager::{Installation, LanguageManager};
+use crate::utils::provider::{detect_provider, Provider};
+use crate::utils::resolve::requested_version;
+use crate::utils::runner::SystemRunner;
+use crate::utils::version::Version;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Action {
+    Check,
+    Install,
+    Uninstall,
+    Update,
+    Status,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Status {
+    UpToDate,
+    Outdated,
+    NotInstalled,
+    Installed,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    pub language: String,
+    pub action: Action,
+    pub path: Option<PathBuf>,
+    pub installed: Option<String>,
+    pub latest: Option<String>,
+    pub requested: Option<String>,
+    pub provider: Option<Provider>,
+    pub status: Status,
+    pub errors: Vec<String>,
+}
+
+impl Report {
+    pub async fn collect(
+        manager: &dyn LanguageManager,
+        action: Action,
+        error: Option<&Error>,
+        include_latest: bool,
+    ) -> Report {
+        let mut errors = Vec::new();
+        if let Some(e) = error {
+            errors.push(e.to_string());
+        }
+
+        let installation = match manager.detect_installed().await {
+            Ok(installation) => Some(installation),
+            Err(Error::NotInstalled(_)) => None,
+            Err(e) => {
+                errors.push(e.to_string());
+                None
+            }
+        };
+        let latest = if include_latest {
+            match manager.fetch_latest().await {
+                Ok(latest) => Some(latest),
+                Err(e) => {
+                    errors.push(format!("Could not determine the latest version: {}", e));
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let status = match (error, &installation, &latest) {
+            (Some(Error::NotInstalled(_)), None, _) => Status::NotInstalled,
+            (Some(_), _, _) => Status::Failed,
+            (None, None, _) => Status::NotInstalled,
+            (None, Some(installed), Some(latest)) => {
+                let scheme = manager.version_scheme();
+                match (Version::parse(scheme, &installed.version), Version::parse(scheme, latest)) {
+                    (Ok(installed), Ok(latest)) if installed < latest => Status::Outdated,
+                    (Ok(_), Ok(_)) => Status::UpToDate,
+                    _ => Status::Installed,
+                }
+            }
+            (None, Some(_), None) => Status::Installed,
+        };
+
+        Report {
+            language: manager.name().to_string(),
+            action,
+            provider: installation.as_ref().map(|installation| detect_provider(&SystemRunner, &installation.path)),
+            path: installation.as_ref().map(|installation| installation.path.clone()),
+            installed: installation.map(|installation| installation.version),
+            latest,
+            requested: requested_version(manager).map(|requested| requested.version),
+            status,
+            errors,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "verdict", rename_all = "kebab-case")]
+pub enum Verdict {
+    Satisfies { requested: String, source: String },
+    Mismatch { requested: String, source: String },
+    UpToDate,
+    Outdated { latest: String },
+    Unknown { reason: String },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Check {
+    pub language: String,
+    pub installation: Installation,
+    #[serde(flatten)]
+    pub verdict: Verdict,
+}
+
+impl Check {
+    pub fn outcome(&self) -> Outcome {
+        match self.verdict {
+            Verdict::Mismatch { .. } | Verdict::Outdated { .. } => Outcome::Outdated,
+            _ => Outcome::Success,
+        }
+    }
+}
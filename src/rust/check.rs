@@ -1,26 +1,46 @@
-﻿use std::process::Command;
-use std::error::Error;
+use std::io::ErrorKind;
+use crate::error::{Error, Result};
+use crate::output::say;
+use crate::utils::runner::CommandRunner;
 
-pub async fn check_rust_version() -> Result<String, Box<dyn Error + Send + Sync>> {
-   let output = Command::new("rustc")
-        .arg("--version")
-        .output();
-
-    match output {
+pub async fn check_rust_version(runner: &dyn CommandRunner) -> Result<String> {
+    match runner.output("rustc", &["--version"]) {
         Ok(out) => {
-            if out.status.success() {
-                let version_info = String::from_utf8_lossy(&out.stdout).trim().to_string();
-                println!("Installed Rust version: {}", version_info);
+            if out.success {
+                let version_info = out.stdout.trim().to_string();
+                say!("Installed Rust version: {}", version_info);
                 Ok(version_info)
             } else {
-                let error_info = String::from_utf8_lossy(&out.stderr);
-                eprintln!("Error executing rustc: {}", error_info);
-                Err("Failed to get Rust version".into())
+                Err(Error::command_failed("rustc --version", out.stderr.trim()))
             }
         }
-        Err(e) => {
-            eprintln!("Failed to execute rustc: {}", e);
-            Err(Box::new(e))
-        }
+        Err(e) if e.kind() == ErrorKind::NotFound => Err(Error::NotInstalled("Rust".to_string())),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rust::update::get_installed_rust_version;
+    use crate::utils::runner::ScriptedRunner;
+
+    const RUSTC: &str = include_str!("../../tests/fixtures/rust/rustc-version.txt");
+
+    #[tokio::test]
+    async fn installed_version_is_the_second_field() {
+        let runner = ScriptedRunner::new().stdout("rustc --version", RUSTC);
+        assert_eq!(check_rust_version(&runner).await.unwrap(), RUSTC.trim());
+        assert_eq!(get_installed_rust_version(&runner).await.unwrap(), "1.95.0");
+    }
+
+    #[tokio::test]
+    async fn missing_and_failing_rustc_are_reported() {
+        assert!(matches!(check_rust_version(&ScriptedRunner::new()).await, Err(Error::NotInstalled(_))));
+        assert!(matches!(get_installed_rust_version(&ScriptedRunner::new()).await, Err(Error::NotInstalled(_))));
+
+        let broken = ScriptedRunner::new().failing("rustc --version", "error: no default toolchain configured");
+        assert!(matches!(check_rust_version(&broken).await, Err(Error::CommandFailed { .. })));
+        assert!(matches!(get_installed_rust_version(&broken).await, Err(Error::CommandFailed { .. })));
     }
 }
@@ -0,0 +1,88 @@
+use async_trait::async_trait;
+use which::which;
+use crate::error::{Error, Result};
+use crate::manager::{find_installation, Installation, InstalledVersion, LanguageManager, RemoteVersion};
+use crate::output::say;
+use crate::rust::check::check_rust_version;
+use crate::rust::update::{
+    fetch_latest_version_rust, get_installed_rust_version, install_rust, install_rust_toolchain,
+    list_rust_releases, list_rust_toolchains, rust_toolchain, rustup_uninstall_steps, set_default_rust_toolchain, update_rust,
+};
+use crate::utils::fetcher::MetadataFetcher;
+use crate::utils::runner::SystemRunner;
+use crate::utils::resolve::requested_version;
+use crate::utils::uninstall::{toolchain_steps, Step};
+use crate::utils::version::Scheme;
+
+pub struct RustManager;
+
+#[async_trait]
+impl LanguageManager for RustManager {
+    fn name(&self) -> &'static str {
+        "rust"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Rust"
+    }
+
+    fn version_scheme(&self) -> Scheme {
+        Scheme::Semver
+    }
+
+    fn version_files(&self) -> &'static [&'static str] {
+        &["rust-toolchain.toml", "rust-toolchain"]
+    }
+
+    async fn check(&self) -> Result<()> {
+        check_rust_version(&SystemRunner).await.map(|_| ())
+    }
+
+    async fn detect_installed(&self) -> Result<Installation> {
+        let path = which("rustc").map_err(|_| Error::NotInstalled("Rust".to_string()))?;
+        let version = get_installed_rust_version(&SystemRunner).await?;
+        Ok(Installation { version, path })
+    }
+
+    async fn fetch_latest(&self) -> Result<String> {
+        fetch_latest_version_rust(&MetadataFetcher).await
+    }
+
+    async fn install(&self, version: Option<&str>) -> Result<()> {
+        match version {
+            Some(v) if which("rustup").is_ok() => install_rust_toolchain(v).await,
+            _ => install_rust().await,
+        }
+    }
+
+    async fn list_remote(&self) -> Result<Vec<RemoteVersion>> {
+        list_rust_releases(&MetadataFetcher).await
+    }
+
+    async fn uninstall_plan(&self, version: Option<&str>) -> Result<Vec<Step>> {
+        if which("rustup").is_ok() {
+            return Ok(rustup_uninstall_steps(version));
+        }
+
+        let installation = find_installation(self, version).await?;
+        toolchain_steps(&SystemRunner, &rust_toolchain(), &installation)
+    }
+
+    async fn list_installed(&self) -> Result<Vec<InstalledVersion>> {
+        list_rust_toolchains(&SystemRunner).await
+    }
+
+    async fn use_version(&self, version: &str) -> Result<()> {
+        set_default_rust_toolchain(version).await
+    }
+
+    async fn update(&self) -> Result<()> {
+        match requested_version(self) {
+            Some(requested) => {
+                say!("Rust {} requested by {}", requested.version, requested.source);
+                install_rust_toolchain(&requested.version).await
+            }
+            None => update_rust().await,
+        }
+    }
+}
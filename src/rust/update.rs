@@ -1,75 +1,292 @@
+use std::env;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
 use std::process::Command;
-use std::error::Error;
-use crate::rust::check::check_rust_version;
-
-pub async fn fetch_latest_version_rust() -> Result<String, Box<dyn Error + Send + Sync>> {
-    let output = Command::new("rustup")
-        .arg("show")
-        .arg("active-toolchain")
-        .output()?;
-
-    if output.status.success() {
-        let version_info = String::from_utf8_lossy(&output.stdout).trim().to_string();
-
-        let version_parts: Vec<&str> = version_info.split_whitespace().collect();
-        if !version_parts.is_empty() {
-            let version = version_parts[0].split('-').next().unwrap_or("None");
-            Ok(version.to_string())
-        } else {
-            Err("Couldn't extract Rust version".into())
+use crate::error::{Error, Result};
+use crate::manager::{InstalledVersion, RemoteVersion};
+use crate::output::say;
+use crate::settings;
+use crate::utils::command::run;
+use crate::utils::fetcher::{Fetcher, MetadataFetcher};
+use crate::utils::mirror::{download_url, metadata_url};
+use crate::utils::runner::{CommandRunner, SystemRunner};
+use crate::utils::uninstall::{Step, Toolchain};
+use crate::utils::version::{Scheme, Version};
+
+pub async fn fetch_latest_version_rust(fetcher: &dyn Fetcher) -> Result<String> {
+    fetch_rust_channel_version(fetcher, &rust_channel()).await
+}
+
+pub async fn list_rust_releases(fetcher: &dyn Fetcher) -> Result<Vec<RemoteVersion>> {
+    let mut releases = Vec::new();
+    for channel in ["stable", "beta", "nightly"] {
+        releases.push(RemoteVersion {
+            version: fetch_rust_channel_version(fetcher, channel).await?,
+            note: Some(channel.to_string()),
+        });
+    }
+    Ok(releases)
+}
+
+async fn fetch_rust_channel_version(fetcher: &dyn Fetcher, channel: &str) -> Result<String> {
+    let url = metadata_url("rust", &format!("dist/channel-rust-{}.toml", channel));
+    let body = fetcher.fetch(&url).await?;
+
+    let mut in_rustc = false;
+    for line in body.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_rustc = line == "[pkg.rustc]";
+        } else if in_rustc && let Some(value) = line.strip_prefix("version = ") {
+            let version = value
+                .trim_matches('"')
+                .split_whitespace()
+                .next()
+                .ok_or_else(|| Error::Parse("Couldn't extract Rust version".to_string()))?;
+            return Ok(version.to_string());
         }
-    } else {
-        Err("Rustup is not installed or failed to execute".into())
     }
+
+    Err(Error::Parse("Couldn't extract Rust version".to_string()))
 }
 
-pub async fn update_rust() -> Result<(), Box<dyn Error + Send + Sync>> {
-    println!("Checking installed Rust version...");
+pub async fn get_installed_rust_version(runner: &dyn CommandRunner) -> Result<String> {
+    let output = match runner.output("rustc", &["--version"]) {
+        Ok(output) => output,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Err(Error::NotInstalled("Rust".to_string())),
+        Err(e) => return Err(e.into()),
+    };
+
+    if output.success {
+        let version_info = output.stdout.trim();
+        version_info
+            .split_whitespace()
+            .nth(1)
+            .map(|v| v.to_string())
+            .ok_or_else(|| Error::Parse(format!("Could not determine installed Rust version from '{}'", version_info)))
+    } else {
+        Err(Error::command_failed("rustc --version", output.stderr.trim()))
+    }
+}
 
-    let installed_version = check_rust_version().await.unwrap_or_else(|_| "None".to_string());
+pub async fn update_rust() -> Result<()> {
+    say!("Checking installed Rust version...");
 
-    let installed_version_cleaned = installed_version
-        .split_whitespace()
-        .nth(1)
-        .unwrap_or("None")
-        .to_string();
+    let installed_version = match get_installed_rust_version(&SystemRunner).await {
+        Ok(version) => version,
+        Err(Error::NotInstalled(_)) => {
+            say!("Rust is not installed. Installing the latest version...");
+            return install_rust().await;
+        }
+        Err(e) => return Err(e),
+    };
 
-    println!("Fetching latest Rust version...");
-    let latest_version = fetch_latest_version_rust().await?;
+    say!("Fetching latest Rust version...");
+    let latest_version = fetch_latest_version_rust(&MetadataFetcher).await?;
 
-    if installed_version_cleaned == latest_version {
-        println!("✅ Rust is already up to date ({}).", installed_version_cleaned);
+    if Version::parse(Scheme::Semver, &installed_version)? >= Version::parse(Scheme::Semver, &latest_version)? {
+        say!("✅ Rust is already up to date ({}).", installed_version);
     } else {
-        println!("🔄 Updating Rust from {} to {}...", installed_version_cleaned, latest_version);
-        let status = Command::new("rustup")
-            .arg("update")
-            .status()?;
+        say!("🔄 Updating Rust from {} to {}...", installed_version, latest_version);
+        let status = run(rustup().args(["update", &rust_channel()]))?;
 
         if status.success() {
-            println!("🎉 Rust successfully updated to {}!", latest_version);
+            say!("🎉 Rust successfully updated to {}!", latest_version);
         } else {
-            eprintln!("❌ Error: Rust update failed.");
+            return Err(Error::command_failed("rustup update", format!("exited with {}", status)));
         }
     }
     Ok(())
 }
 
-pub async fn install_rust() -> Result<(), Box<dyn Error + Send + Sync>> {
+pub async fn install_rust() -> Result<()> {
     let status = if cfg!(target_os = "windows") {
-        Command::new("winget")
-            .args(["install", "--id", "Rustlang.Rustup"])
-            .status()
+        run(Command::new("winget").args(["install", "--id", "Rustlang.Rustup"]))
     } else {
-        Command::new("sh")
+        run(with_mirror(&mut Command::new("sh"))
             .arg("-c")
-            .arg("curl --proto '=https' --tlsv1.2 -sSf https://sh.rustup.rs | sh -s -- -y")
-            .status()
+            .arg(format!(
+                "curl --proto '=https' --tlsv1.2 -sSf {} | sh -s -- -y --default-toolchain {}",
+                download_url("rust", "rustup/rustup-init.sh"),
+                rust_channel()
+            )))
     }?;
 
     if status.success() {
-        println!("Rust successfully installed!");
+        say!("Rust successfully installed!");
+        Ok(())
+    } else {
+        Err(Error::command_failed("rustup installer", format!("exited with {}", status)))
+    }
+}
+
+pub async fn install_rust_toolchain(version: &str) -> Result<()> {
+    let status = run(rustup().args(["toolchain", "install", version]))?;
+
+    if status.success() {
+        say!("Rust toolchain {} successfully installed!", version);
         Ok(())
     } else {
-        Err("Failed to install Rust".into())
+        Err(Error::command_failed(
+            format!("rustup toolchain install {}", version),
+            format!("exited with {}", status),
+        ))
+    }
+}
+
+fn rustup() -> Command {
+    let mut command = Command::new("rustup");
+    with_mirror(&mut command);
+    command
+}
+
+fn with_mirror(command: &mut Command) -> &mut Command {
+    if settings::get().config.is_set("rust.download_url") {
+        command
+            .env("RUSTUP_DIST_SERVER", download_url("rust", "").trim_end_matches('/'))
+            .env("RUSTUP_UPDATE_ROOT", download_url("rust", "rustup"));
+    }
+    command
+}
+
+fn rust_channel() -> String {
+    settings::get().config.get("rust.channel").unwrap_or_else(|| "stable".to_string())
+}
+
+pub fn rustup_uninstall_steps(version: Option<&str>) -> Vec<Step> {
+    let rustup_home = tool_home("RUSTUP_HOME", ".rustup");
+    match version {
+        Some(version) => {
+            let mut step =
+                Step::command(format!("toolchain {}", version), &["rustup", "toolchain", "uninstall", version]);
+            let prefix = format!("{}-", version);
+            for entry in fs::read_dir(rustup_home.join("toolchains")).into_iter().flatten().flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if name == version || name.starts_with(&prefix) {
+                    step = step.freeing(entry.path());
+                }
+            }
+            vec![step]
+        }
+        None => vec![
+            Step::command("rustup", &["rustup", "self", "uninstall", "-y"])
+                .freeing(rustup_home)
+                .freeing(tool_home("CARGO_HOME", ".cargo")),
+        ],
+    }
+}
+
+pub fn rust_toolchain() -> Toolchain {
+    Toolchain {
+        name: "Rust",
+        binaries: &["rustc", "cargo", "rustdoc"],
+        markers: &["lib/rustlib/uninstall.sh"],
+        uninstaller: Some("lib/rustlib/uninstall.sh"),
+        conda_package: "rust",
+        workspaces: |_| Vec::new(),
+    }
+}
+
+fn tool_home(variable: &str, default: &str) -> PathBuf {
+    env::var_os(variable)
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(default)))
+        .unwrap_or_else(|| PathBuf::from(default))
+}
+
+pub async fn list_rust_toolchains(runner: &dyn CommandRunner) -> Result<Vec<InstalledVersion>> {
+    let output = runner
+        .output("rustup", &["toolchain", "list"])
+        .map_err(|_| Error::NotInstalled("rustup".to_string()))?;
+    if !output.success {
+        return Err(Error::command_failed("rustup toolchain list", output.stderr.trim()));
+    }
+
+    Ok(output
+        .stdout
+        .lines()
+        .filter_map(|line| {
+            let version = line.split_whitespace().next()?;
+            Some(InstalledVersion {
+                version: version.to_string(),
+                default: line.contains("default"),
+            })
+        })
+        .collect())
+}
+
+pub async fn set_default_rust_toolchain(version: &str) -> Result<()> {
+    let status = run(Command::new("rustup").args(["default", version]))
+        .map_err(|_| Error::NotInstalled("rustup".to_string()))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Error::command_failed(
+            format!("rustup default {}", version),
+            format!("exited with {}", status),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::fetcher::FixtureFetcher;
+    use crate::utils::runner::ScriptedRunner;
+
+    const STABLE: &str = include_str!("../../tests/fixtures/rust/channel-rust-stable.toml");
+    const BETA: &str = include_str!("../../tests/fixtures/rust/channel-rust-beta.toml");
+    const NIGHTLY: &str = include_str!("../../tests/fixtures/rust/channel-rust-nightly.toml");
+    const TOOLCHAINS: &str = include_str!("../../tests/fixtures/rust/rustup-toolchain-list.txt");
+
+    fn fixtures(stable: &str) -> FixtureFetcher {
+        FixtureFetcher::new()
+            .with(metadata_url("rust", "dist/channel-rust-stable.toml"), stable)
+            .with(metadata_url("rust", "dist/channel-rust-beta.toml"), BETA)
+            .with(metadata_url("rust", "dist/channel-rust-nightly.toml"), NIGHTLY)
+    }
+
+    #[tokio::test]
+    async fn latest_is_the_rustc_version_not_cargo() {
+        assert_eq!(fetch_latest_version_rust(&fixtures(STABLE)).await.unwrap(), "1.83.0");
+    }
+
+    #[tokio::test]
+    async fn remote_list_covers_every_channel() {
+        let releases = list_rust_releases(&fixtures(STABLE)).await.unwrap();
+        let notes: Vec<&str> = releases.iter().filter_map(|release| release.note.as_deref()).collect();
+        assert_eq!(notes, ["stable", "beta", "nightly"]);
+        assert_eq!(releases[0].version, "1.83.0");
+        assert!(releases[1].version.contains("-beta"));
+        assert!(releases[2].version.ends_with("-nightly"));
+    }
+
+    #[tokio::test]
+    async fn manifests_without_rustc_are_parse_errors() {
+        for body in ["", "manifest-version = \"2\"\n[pkg.cargo]\nversion = \"0.84.0\"\n", "[pkg.rustc]\nversion = \"\"\n"] {
+            assert!(
+                matches!(fetch_latest_version_rust(&fixtures(body)).await, Err(Error::Parse(_))),
+                "accepted {:?}",
+                body
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn installed_toolchains_come_from_rustup() {
+        let runner = ScriptedRunner::new().stdout("rustup toolchain list", TOOLCHAINS);
+        let toolchains = list_rust_toolchains(&runner).await.unwrap();
+        let versions: Vec<&str> = toolchains.iter().map(|toolchain| toolchain.version.as_str()).collect();
+        assert_eq!(
+            versions,
+            [
+                "stable-x86_64-unknown-linux-gnu",
+                "nightly-2024-11-28-x86_64-unknown-linux-gnu",
+                "1.79.0-x86_64-unknown-linux-gnu"
+            ]
+        );
+        assert!(toolchains[0].default && !toolchains[1].default && !toolchains[2].default);
+        assert!(matches!(list_rust_toolchains(&ScriptedRunner::new()).await, Err(Error::NotInstalled(_))));
     }
 }
@@ -0,0 +1,59 @@
+//! Process-wide settings shared by every operation; see [`init`].
+
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use crate::config::Config;
+use crate::error::{Error, Result};
+use crate::output::OutputFormat;
+use crate::utils::paths::default_install_root;
+
+#[derive(Debug, Clone)]
+pub struct Settings {
+    pub install_root: PathBuf,
+    pub system_wide: bool,
+    pub confirm: bool,
+    pub format: OutputFormat,
+    pub dry_run: bool,
+    pub offline: bool,
+    pub refresh: bool,
+    pub quiet: bool,
+    pub config: Config,
+}
+
+impl Settings {
+    pub fn from_config(config: Config) -> Settings {
+        Settings {
+            install_root: config.get("root").map(PathBuf::from).unwrap_or_else(default_install_root),
+            system_wide: config.get_bool("system"),
+            confirm: config.get_bool("confirm"),
+            format: OutputFormat::Text,
+            dry_run: false,
+            offline: false,
+            refresh: false,
+            quiet: false,
+            config,
+        }
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings::from_config(Config::load().unwrap_or_default())
+    }
+}
+
+static SETTINGS: OnceLock<Settings> = OnceLock::new();
+
+/// Installs the settings for the rest of the process.
+///
+/// Call this before any other function in the crate: the first lookup loads the defaults, after
+/// which the settings can no longer change and this fails.
+pub fn init(settings: Settings) -> Result<()> {
+    SETTINGS
+        .set(settings)
+        .map_err(|_| Error::Other("Settings are already in use and can no longer be changed".to_string()))
+}
+
+pub fn get() -> &'static Settings {
+    SETTINGS.get_or_init(Settings::default)
+}
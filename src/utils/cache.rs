@@ -0,0 +1,111 @@
+//! On-disk cache of release metadata, honouring `--offline` and `--refresh`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use sha2::{Digest, Sha256};
+use crate::error::{Error, Result};
+use crate::output::warn;
+use crate::settings;
+use crate::utils::command::is_dry_run;
+use crate::utils::http::get_text;
+use crate::utils::paths::metadata_cache_dir;
+
+static OLDEST_USED: Mutex<Option<Duration>> = Mutex::new(None);
+
+pub async fn fetch_metadata(url: &str) -> Result<String> {
+    lookup(url, settings::get().refresh).await
+}
+
+pub async fn refetch_metadata(url: &str) -> Result<String> {
+    lookup(url, true).await
+}
+
+async fn lookup(url: &str, refresh: bool) -> Result<String> {
+    let settings = settings::get();
+    let path = entry_path(url);
+    let cached = read_entry(&path);
+
+    if settings.offline {
+        let (body, age) = cached.ok_or_else(|| {
+            Error::Network(format!("{} is not in the cache; run once without --offline to fetch it", url))
+        })?;
+        record_age(age);
+        return Ok(body);
+    }
+    if !refresh
+        && let Some((body, age)) = &cached
+        && *age < ttl()
+    {
+        return Ok(body.clone());
+    }
+
+    match get_text(url).await {
+        Ok(body) => {
+            if !is_dry_run()
+                && let Err(e) = store(&path, &body)
+            {
+                warn(format_args!("Could not cache {}: {}", url, e));
+            }
+            Ok(body)
+        }
+        Err(e) => match cached {
+            Some((body, age)) => {
+                warn(format_args!("{}; using the copy cached {} ago", e, format_age(age)));
+                record_age(age);
+                Ok(body)
+            }
+            None => Err(e),
+        },
+    }
+}
+
+pub fn oldest_used() -> Option<Duration> {
+    *OLDEST_USED.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+pub fn format_age(age: Duration) -> String {
+    let seconds = age.as_secs();
+    match seconds {
+        0..60 => format!("{}s", seconds),
+        60..3600 => format!("{}m", seconds / 60),
+        3600..86400 => format!("{}h {}m", seconds / 3600, seconds % 3600 / 60),
+        _ => format!("{}d {}h", seconds / 86400, seconds % 86400 / 3600),
+    }
+}
+
+fn ttl() -> Duration {
+    settings::get().config.get_duration("cache.ttl").unwrap_or_default()
+}
+
+fn entry_path(url: &str) -> PathBuf {
+    let digest: String = Sha256::digest(url.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    metadata_cache_dir().join(digest)
+}
+
+fn read_entry(path: &Path) -> Option<(String, Duration)> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    let age = SystemTime::now().duration_since(modified).unwrap_or_default();
+    Some((fs::read_to_string(path).ok()?, age))
+}
+
+fn store(path: &Path, body: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let partial = path.with_extension("part");
+    fs::write(&partial, body)?;
+    fs::rename(&partial, path)?;
+    Ok(())
+}
+
+fn record_age(age: Duration) {
+    let mut oldest = OLDEST_USED.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if oldest.is_none_or(|oldest| age > oldest) {
+        *oldest = Some(age);
+    }
+}
@@ -0,0 +1,74 @@
+//! SHA-256 verification of downloads.
+
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::Path;
+use sha2::{Digest, Sha256};
+use crate::error::{Error, Result};
+use crate::output::say;
+use crate::utils::command::{is_dry_run, plan};
+use crate::utils::paths::language_dir;
+
+pub fn sha256_file(path: &Path) -> Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+pub fn verify_sha256(path: &Path, expected: &str) -> Result<String> {
+    if is_dry_run() {
+        plan(format!("would verify SHA-256 {}", expected.trim()));
+        return Ok(expected.trim().to_ascii_lowercase());
+    }
+
+    let actual = sha256_file(path)?;
+    if !actual.eq_ignore_ascii_case(expected.trim()) {
+        fs::remove_file(path)?;
+        return Err(Error::ChecksumMismatch {
+            file: path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            expected: expected.trim().to_ascii_lowercase(),
+            actual,
+        });
+    }
+
+    say!("✅ SHA-256 verified: {}", actual);
+    Ok(actual)
+}
+
+pub fn find_in_shasums(shasums: &str, file_name: &str) -> Option<String> {
+    shasums.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        let digest = fields.next()?;
+        let name = fields.next()?.trim_start_matches('*');
+        (name == file_name).then(|| digest.to_string())
+    })
+}
+
+pub fn record_digest(language: &str, version: &str, file_name: &str, digest: &str) -> Result<()> {
+    if is_dry_run() {
+        return Ok(());
+    }
+
+    let dir = language_dir(language);
+    fs::create_dir_all(&dir)?;
+    fs::write(
+        dir.join(format!("{}.sha256", version)),
+        format!("{}  {}\n", digest, file_name),
+    )?;
+    Ok(())
+}
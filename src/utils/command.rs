@@ -0,0 +1,70 @@
+//! Running external commands and deleting files, with `--dry-run` support.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::{Command, ExitStatus, Stdio};
+use crate::error::Result;
+use crate::output::{is_structured, say};
+use crate::settings;
+use crate::utils::http::export_proxy;
+
+pub fn is_dry_run() -> bool {
+    settings::get().dry_run
+}
+
+pub fn plan(action: impl AsRef<str>) {
+    say!("[dry-run] {}", action.as_ref());
+}
+
+pub fn run(command: &mut Command) -> Result<ExitStatus> {
+    if is_dry_run() {
+        plan(format!("would run: {}", describe(command)));
+        return Ok(success());
+    }
+    if is_structured() {
+        command.stdout(Stdio::from(io::stderr()));
+    }
+    Ok(export_proxy(command).status()?)
+}
+
+pub fn remove_path(path: &Path) -> Result<()> {
+    if is_dry_run() {
+        plan(format!("would delete {}", path.display()));
+        return Ok(());
+    }
+
+    if path.is_dir() {
+        fs::remove_dir_all(path)?;
+    } else {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+pub fn describe(command: &Command) -> String {
+    std::iter::once(command.get_program())
+        .chain(command.get_args())
+        .map(|part| {
+            let part = part.to_string_lossy();
+            if part.contains(char::is_whitespace) {
+                format!("\"{}\"", part)
+            } else {
+                part.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(unix)]
+fn success() -> ExitStatus {
+    use std::os::unix::process::ExitStatusExt;
+    ExitStatus::from_raw(0)
+}
+
+#[cfg(windows)]
+fn success() -> ExitStatus {
+    use std::os::windows::process::ExitStatusExt;
+    ExitStatus::from_raw(0)
+}
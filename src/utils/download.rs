@@ -0,0 +1,105 @@
+//! Downloads into the shared downloads directory.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use indicatif::{ProgressBar, ProgressStyle};
+use reqwest::StatusCode;
+use reqwest::header::{CONTENT_RANGE, RANGE};
+use crate::error::{Error, Result};
+use crate::output::say;
+use crate::utils::command::{is_dry_run, plan};
+use crate::utils::http::{client, with_retries, Failure};
+use crate::utils::paths::downloads_dir;
+
+const PROGRESS_TEMPLATE: &str =
+    "{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({bytes_per_sec}, ETA {eta})";
+
+pub async fn download(url: &str, file_name: &str) -> Result<PathBuf> {
+    let dir = downloads_dir();
+    let destination = dir.join(file_name);
+    if is_dry_run() {
+        plan(format!("would download {} to {}", url, destination.display()));
+        return Ok(destination);
+    }
+    fs::create_dir_all(&dir)?;
+
+    with_retries(url, || fetch(url, file_name, &destination)).await
+}
+
+async fn fetch(url: &str, file_name: &str, destination: &Path) -> std::result::Result<PathBuf, Failure> {
+    let partial = downloads_dir().join(format!("{}.part", file_name));
+    let resume_from = fs::metadata(&partial).map(|metadata| metadata.len()).unwrap_or(0);
+    let mut request = client()?.get(url);
+    if resume_from > 0 {
+        request = request.header(RANGE, format!("bytes={}-", resume_from));
+    }
+
+    let mut response = request.send().await?;
+    if response.status() == StatusCode::RANGE_NOT_SATISFIABLE && resume_from > 0 {
+        fs::rename(&partial, destination)?;
+        return Ok(destination.to_path_buf());
+    }
+    response.error_for_status_ref()?;
+
+    let resumed = response.status() == StatusCode::PARTIAL_CONTENT;
+    let offset = if resumed { resume_from } else { 0 };
+    let total = if resumed {
+        total_from_content_range(&response)
+    } else {
+        response.content_length()
+    };
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(resumed)
+        .truncate(!resumed)
+        .open(&partial)?;
+
+    let progress = match total {
+        Some(total) => ProgressBar::new(total),
+        None => ProgressBar::new_spinner(),
+    };
+    progress.set_style(
+        ProgressStyle::with_template(PROGRESS_TEMPLATE)
+            .map_err(|e| Error::Other(e.to_string()))?
+            .progress_chars("=> "),
+    );
+    if resumed {
+        say!("Resuming download of {} at {} bytes", file_name, resume_from);
+    }
+    progress.set_position(offset);
+
+    while let Some(chunk) = response.chunk().await? {
+        file.write_all(&chunk)?;
+        progress.inc(chunk.len() as u64);
+    }
+    file.flush()?;
+    progress.finish();
+
+    if let Some(total) = total {
+        let written = fs::metadata(&partial)?.len();
+        if written != total {
+            return Err(Failure::Transient(format!(
+                "download of {} ended after {} of {} bytes",
+                file_name, written, total
+            )));
+        }
+    }
+
+    fs::rename(&partial, destination)?;
+    Ok(destination.to_path_buf())
+}
+
+fn total_from_content_range(response: &reqwest::Response) -> Option<u64> {
+    response
+        .headers()
+        .get(CONTENT_RANGE)?
+        .to_str()
+        .ok()?
+        .rsplit('/')
+        .next()?
+        .parse()
+        .ok()
+}
@@ -0,0 +1,302 @@
+//! Unpacking tar and zip archives without escaping the destination.
+
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+use flate2::read::GzDecoder;
+use indicatif::{ProgressBar, ProgressStyle};
+use xz2::read::XzDecoder;
+use zip::ZipArchive;
+use crate::error::{Error, Result};
+
+const PROGRESS_TEMPLATE: &str = "{spinner:.green} Extracting [{bar:40.cyan/blue}] {percent}%";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveKind {
+    TarGz,
+    TarXz,
+    Zip,
+}
+
+impl ArchiveKind {
+    pub fn from_path(path: &Path) -> Option<ArchiveKind> {
+        let name = path.file_name()?.to_string_lossy().to_ascii_lowercase();
+        if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(ArchiveKind::TarGz)
+        } else if name.ends_with(".tar.xz") || name.ends_with(".txz") {
+            Some(ArchiveKind::TarXz)
+        } else if name.ends_with(".zip") {
+            Some(ArchiveKind::Zip)
+        } else {
+            None
+        }
+    }
+}
+
+pub fn extract(archive: &Path, dest: &Path, strip_components: usize) -> Result<()> {
+    let kind = ArchiveKind::from_path(archive)
+        .ok_or_else(|| Error::Other(format!("Unsupported archive format: {}", archive.display())))?;
+    fs::create_dir_all(dest)?;
+
+    let progress = ProgressBar::new(fs::metadata(archive)?.len());
+    progress.set_style(
+        ProgressStyle::with_template(PROGRESS_TEMPLATE)
+            .map_err(|e| Error::Other(e.to_string()))?
+            .progress_chars("=> "),
+    );
+
+    let result = match kind {
+        ArchiveKind::TarGz => extract_tar(GzDecoder::new(progress.wrap_read(File::open(archive)?)), dest, strip_components),
+        ArchiveKind::TarXz => extract_tar(XzDecoder::new(progress.wrap_read(File::open(archive)?)), dest, strip_components),
+        ArchiveKind::Zip => extract_zip(archive, dest, strip_components, &progress),
+    };
+    progress.finish_and_clear();
+    result.map_err(|e| match e {
+        Error::Io(e) => Error::Other(format!("Failed to extract {}: {}", archive.display(), e)),
+        other => other,
+    })
+}
+
+fn extract_tar<R: Read>(reader: R, dest: &Path, strip_components: usize) -> Result<()> {
+    let mut archive = tar::Archive::new(reader);
+    archive.set_preserve_permissions(true);
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.into_owned();
+        let Some(relative) = strip_path(&path, strip_components)? else {
+            continue;
+        };
+        refuse_symlinked_parents(dest, &relative)?;
+
+        let target = dest.join(&relative);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        if fs::symlink_metadata(&target).is_ok_and(|metadata| metadata.file_type().is_symlink()) {
+            fs::remove_file(&target)?;
+        }
+
+        let entry_type = entry.header().entry_type();
+        if entry_type.is_hard_link() || entry_type.is_symlink() {
+            let link = entry
+                .link_name()?
+                .ok_or_else(|| Error::Other(format!("Link without a target in archive: {}", path.display())))?
+                .into_owned();
+            if entry_type.is_hard_link() {
+                let source = strip_path(&link, strip_components)?.ok_or_else(|| unsafe_link(&path, &link))?;
+                refuse_symlinked_parents(dest, &source)?;
+                let source = dest.join(source);
+                if !source.canonicalize()?.starts_with(dest.canonicalize()?) {
+                    return Err(unsafe_link(&path, &link));
+                }
+                fs::hard_link(source, &target)?;
+                continue;
+            }
+            if !link_stays_inside(&relative, &link) {
+                return Err(unsafe_link(&path, &link));
+            }
+        }
+        entry.unpack(&target)?;
+    }
+    Ok(())
+}
+
+fn refuse_symlinked_parents(dest: &Path, relative: &Path) -> Result<()> {
+    for parent in relative.ancestors().skip(1).filter(|parent| !parent.as_os_str().is_empty()) {
+        if fs::symlink_metadata(dest.join(parent)).is_ok_and(|metadata| metadata.file_type().is_symlink()) {
+            return Err(Error::Other(format!(
+                "Unsafe path in archive: {} is written through the symlink {}",
+                relative.display(),
+                parent.display()
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn link_stays_inside(relative: &Path, link: &Path) -> bool {
+    let mut depth = relative.components().count() - 1;
+    for component in link.components() {
+        match component {
+            Component::CurDir => {}
+            Component::Normal(_) => depth += 1,
+            Component::ParentDir if depth > 0 => depth -= 1,
+            _ => return false,
+        }
+    }
+    true
+}
+
+fn unsafe_link(path: &Path, link: &Path) -> Error {
+    Error::Other(format!("Unsafe link in archive: {} -> {}", path.display(), link.display()))
+}
+
+fn extract_zip(archive: &Path, dest: &Path, strip_components: usize, progress: &ProgressBar) -> Result<()> {
+    let mut zip = ZipArchive::new(File::open(archive)?).map_err(zip_error)?;
+    progress.set_length(zip.len() as u64);
+
+    for index in 0..zip.len() {
+        let mut file = zip.by_index(index).map_err(zip_error)?;
+        progress.inc(1);
+        let path = file
+            .enclosed_name()
+            .ok_or_else(|| Error::Other(format!("Unsafe path in archive entry {}", index)))?;
+        let Some(relative) = strip_path(&path, strip_components)? else {
+            continue;
+        };
+
+        let target = dest.join(relative);
+        if file.is_dir() {
+            fs::create_dir_all(&target)?;
+            continue;
+        }
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        io::copy(&mut file, &mut File::create(&target)?)?;
+
+        #[cfg(unix)]
+        if let Some(mode) = file.unix_mode() {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&target, fs::Permissions::from_mode(mode))?;
+        }
+    }
+    Ok(())
+}
+
+fn strip_path(path: &Path, strip_components: usize) -> Result<Option<PathBuf>> {
+    let mut stripped = PathBuf::new();
+    let components = path.components().filter(|component| *component != Component::CurDir);
+    for (index, component) in components.enumerate() {
+        match component {
+            Component::Normal(_) if index < strip_components => {}
+            Component::Normal(part) => stripped.push(part),
+            _ => return Err(Error::Other(format!("Unsafe path in archive: {}", path.display()))),
+        }
+    }
+    Ok(if stripped.as_os_str().is_empty() { None } else { Some(stripped) })
+}
+
+fn zip_error(e: zip::result::ZipError) -> Error {
+    match e {
+        zip::result::ZipError::Io(e) => e.into(),
+        other => Error::Parse(other.to_string()),
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use tar::{Builder, EntryType, Header};
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("language-revamp-extract-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn header(path: &str, entry_type: EntryType, size: u64) -> Header {
+        let mut header = Header::new_gnu();
+        let name = &mut header.as_old_mut().name;
+        name[..path.len()].copy_from_slice(path.as_bytes());
+        header.set_entry_type(entry_type);
+        header.set_size(size);
+        header.set_mode(0o755);
+        header
+    }
+
+    fn file(builder: &mut Builder<Vec<u8>>, path: &str, contents: &str) {
+        let mut header = header(path, EntryType::Regular, contents.len() as u64);
+        header.set_cksum();
+        builder.append(&header, contents.as_bytes()).unwrap();
+    }
+
+    fn link(builder: &mut Builder<Vec<u8>>, entry_type: EntryType, path: &str, target: &str) {
+        let mut header = header(path, entry_type, 0);
+        header.set_link_name(target).unwrap();
+        header.set_cksum();
+        builder.append(&header, io::empty()).unwrap();
+    }
+
+    fn unpack(name: &str, build: impl FnOnce(&mut Builder<Vec<u8>>)) -> (PathBuf, Result<()>) {
+        let mut builder = Builder::new(Vec::new());
+        build(&mut builder);
+        let bytes = builder.into_inner().unwrap();
+        let root = scratch(name);
+        let dest = root.join("staging");
+        let result = extract_tar(bytes.as_slice(), &dest, 1);
+        (root, result)
+    }
+
+    #[test]
+    fn regular_files_and_inner_links_are_extracted() {
+        let (root, result) = unpack("regular", |builder| {
+            file(builder, "go/bin/go", "binary");
+            link(builder, EntryType::Symlink, "go/bin/gofmt", "go");
+            link(builder, EntryType::Symlink, "go/lib/go", "../bin/go");
+            link(builder, EntryType::Link, "go/bin/go-alias", "go/bin/go");
+        });
+        result.unwrap();
+        let dest = root.join("staging");
+        assert_eq!(fs::read_to_string(dest.join("bin/go")).unwrap(), "binary");
+        assert_eq!(fs::read_to_string(dest.join("bin/gofmt")).unwrap(), "binary");
+        assert_eq!(fs::read_to_string(dest.join("lib/go")).unwrap(), "binary");
+        assert_eq!(fs::read_to_string(dest.join("bin/go-alias")).unwrap(), "binary");
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn parent_directory_entries_are_refused() {
+        let (root, result) = unpack("traversal", |builder| file(builder, "go/../../escaped", "payload"));
+        assert!(result.is_err());
+        assert!(!root.join("escaped").exists());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn symlinks_leaving_the_destination_are_refused() {
+        for target in ["/tmp", "../..", "bin/../../.."] {
+            let (root, result) = unpack("symlink", |builder| link(builder, EntryType::Symlink, "go/escape", target));
+            assert!(result.is_err(), "accepted a symlink to {}", target);
+            fs::remove_dir_all(root).unwrap();
+        }
+    }
+
+    #[test]
+    fn entries_are_not_written_through_symlinks() {
+        let (root, result) = unpack("through-symlink", |builder| {
+            link(builder, EntryType::Symlink, "go/up", ".");
+            link(builder, EntryType::Symlink, "go/up/escape", "..");
+            file(builder, "go/up/escape/escaped", "payload");
+        });
+        assert!(result.is_err());
+        assert!(!root.join("escaped").exists());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn hardlinks_leaving_the_destination_are_refused() {
+        let outside = scratch("hardlink-outside").join("secret");
+        fs::write(&outside, "secret").unwrap();
+        for target in [outside.to_str().unwrap(), "go/../../secret", "secret"] {
+            let (root, result) = unpack("hardlink", |builder| link(builder, EntryType::Link, "go/stolen", target));
+            assert!(result.is_err(), "accepted a hardlink to {}", target);
+            assert!(!root.join("staging/stolen").exists());
+            fs::remove_dir_all(root).unwrap();
+        }
+        fs::remove_dir_all(outside.parent().unwrap()).unwrap();
+
+        let mut builder = Builder::new(Vec::new());
+        link(&mut builder, EntryType::Symlink, "go/a/b", "..");
+        link(&mut builder, EntryType::Symlink, "go/a/l", "b/..");
+        link(&mut builder, EntryType::Link, "go/stolen", "go/a/l/secret");
+        let root = scratch("hardlink-chain");
+        fs::write(root.join("secret"), "secret").unwrap();
+        let result = extract_tar(builder.into_inner().unwrap().as_slice(), &root.join("staging"), 1);
+        assert!(result.is_err(), "accepted a hardlink through a symlink chain");
+        assert!(!root.join("staging/stolen").exists());
+        fs::remove_dir_all(root).unwrap();
+    }
+}
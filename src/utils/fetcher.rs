@@ -0,0 +1,77 @@
+//! The [`Fetcher`] trait through which release metadata is requested.
+
+use async_trait::async_trait;
+use crate::error::Result;
+use crate::utils::cache::{fetch_metadata, refetch_metadata};
+
+#[async_trait]
+pub trait Fetcher: Send + Sync {
+    async fn fetch(&self, url: &str) -> Result<String>;
+
+    async fn refetch(&self, url: &str) -> Result<String> {
+        self.fetch(url).await
+    }
+}
+
+pub struct MetadataFetcher;
+
+#[async_trait]
+impl Fetcher for MetadataFetcher {
+    async fn fetch(&self, url: &str) -> Result<String> {
+        fetch_metadata(url).await
+    }
+
+    async fn refetch(&self, url: &str) -> Result<String> {
+        refetch_metadata(url).await
+    }
+}
+
+#[cfg(test)]
+pub use fixtures::FixtureFetcher;
+
+#[cfg(test)]
+mod fixtures {
+    use std::collections::HashMap;
+    use async_trait::async_trait;
+    use crate::error::{Error, Result};
+    use super::Fetcher;
+
+    #[derive(Default)]
+    pub struct FixtureFetcher {
+        responses: HashMap<String, String>,
+        refreshed: HashMap<String, String>,
+    }
+
+    impl FixtureFetcher {
+        pub fn new() -> FixtureFetcher {
+            FixtureFetcher::default()
+        }
+
+        pub fn with(mut self, url: impl Into<String>, body: impl Into<String>) -> FixtureFetcher {
+            self.responses.insert(url.into(), body.into());
+            self
+        }
+
+        pub fn refreshed(mut self, url: impl Into<String>, body: impl Into<String>) -> FixtureFetcher {
+            self.refreshed.insert(url.into(), body.into());
+            self
+        }
+    }
+
+    #[async_trait]
+    impl Fetcher for FixtureFetcher {
+        async fn fetch(&self, url: &str) -> Result<String> {
+            self.responses
+                .get(url)
+                .cloned()
+                .ok_or_else(|| Error::Network(format!("no fixture recorded for {}", url)))
+        }
+
+        async fn refetch(&self, url: &str) -> Result<String> {
+            match self.refreshed.get(url) {
+                Some(body) => Ok(body.clone()),
+                None => self.fetch(url).await,
+            }
+        }
+    }
+}
@@ -0,0 +1,204 @@
+//! The shared HTTP client with proxy, TLS, timeout and retry settings.
+
+use std::fs;
+use std::future::Future;
+use std::hash::{BuildHasher, RandomState};
+use std::io;
+use std::process::Command;
+use std::sync::OnceLock;
+use std::time::Duration;
+use reqwest::{Certificate, Client, Identity, NoProxy, Proxy, StatusCode};
+use crate::error::{Error, Result};
+use crate::output::warn;
+use crate::settings;
+
+const PROXY_VARIABLES: [(&str, [&str; 2]); 3] = [
+    ("proxy.https", ["HTTPS_PROXY", "https_proxy"]),
+    ("proxy.http", ["HTTP_PROXY", "http_proxy"]),
+    ("proxy.no_proxy", ["NO_PROXY", "no_proxy"]),
+];
+const FIRST_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_BACKOFF: Duration = Duration::from_secs(10);
+
+static CLIENT: OnceLock<Client> = OnceLock::new();
+
+pub enum Failure {
+    Transient(String),
+    Fatal(Error),
+}
+
+impl From<reqwest::Error> for Failure {
+    fn from(e: reqwest::Error) -> Self {
+        let transient = e.is_timeout()
+            || e.is_connect()
+            || e.is_body()
+            || e.is_request()
+            || e.status().is_some_and(|status| status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS);
+        if transient {
+            Failure::Transient(reason(&e))
+        } else {
+            Failure::Fatal(Error::Network(reason(&e)))
+        }
+    }
+}
+
+impl From<Error> for Failure {
+    fn from(e: Error) -> Self {
+        Failure::Fatal(e)
+    }
+}
+
+impl From<io::Error> for Failure {
+    fn from(e: io::Error) -> Self {
+        Failure::Fatal(e.into())
+    }
+}
+
+pub fn client() -> Result<&'static Client> {
+    if let Some(client) = CLIENT.get() {
+        return Ok(client);
+    }
+    let client = build_client()?;
+    Ok(CLIENT.get_or_init(|| client))
+}
+
+pub async fn get_text(url: &str) -> Result<String> {
+    let client = client()?;
+    with_retries(url, || async {
+        Ok(client.get(url).send().await?.error_for_status()?.text().await?)
+    })
+    .await
+}
+
+pub async fn with_retries<T, F, Fut>(url: &str, mut attempt: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = std::result::Result<T, Failure>>,
+{
+    let attempts = settings::get()
+        .config
+        .get("http.retries")
+        .and_then(|retries| retries.parse::<u32>().ok())
+        .unwrap_or(0)
+        + 1;
+    let mut made = 0;
+    loop {
+        made += 1;
+        match attempt().await {
+            Ok(value) => return Ok(value),
+            Err(Failure::Transient(reason)) if made < attempts => {
+                let delay = backoff(made);
+                warn(format_args!(
+                    "{}: {}; retrying in {:.1}s (attempt {} of {})",
+                    url,
+                    reason,
+                    delay.as_secs_f64(),
+                    made + 1,
+                    attempts
+                ));
+                tokio::time::sleep(delay).await;
+            }
+            Err(Failure::Transient(reason)) => {
+                return Err(Error::Network(format!(
+                    "{} failed after {} attempt{}: {}",
+                    url,
+                    made,
+                    if made == 1 { "" } else { "s" },
+                    reason
+                )));
+            }
+            Err(Failure::Fatal(Error::Network(reason))) => {
+                return Err(Error::Network(format!("{}: {}", url, reason)));
+            }
+            Err(Failure::Fatal(e)) => return Err(e),
+        }
+    }
+}
+
+pub fn export_proxy(command: &mut Command) -> &mut Command {
+    let config = &settings::get().config;
+    for (key, variables) in PROXY_VARIABLES {
+        if let Some(value) = config.get(key) {
+            for variable in variables {
+                command.env(variable, &value);
+            }
+        }
+    }
+    command
+}
+
+fn backoff(attempt: u32) -> Duration {
+    let ceiling = FIRST_BACKOFF.saturating_mul(1 << (attempt - 1).min(16)).min(MAX_BACKOFF);
+    let half = ceiling / 2;
+    let jitter = RandomState::new().hash_one(attempt) % (half.as_millis() as u64 + 1);
+    half + Duration::from_millis(jitter)
+}
+
+fn reason(e: &reqwest::Error) -> String {
+    if let Some(status) = e.status() {
+        return format!("HTTP {}", status);
+    }
+    if e.is_timeout() {
+        return "timed out".to_string();
+    }
+    let mut cause: &dyn std::error::Error = e;
+    while let Some(source) = cause.source() {
+        cause = source;
+    }
+    cause.to_string()
+}
+
+fn build_client() -> Result<Client> {
+    let config = &settings::get().config;
+    let mut builder = Client::builder();
+    if let Some(timeout) = config.get_duration("http.connect_timeout").filter(|timeout| !timeout.is_zero()) {
+        builder = builder.connect_timeout(timeout);
+    }
+    if let Some(timeout) = config.get_duration("http.read_timeout").filter(|timeout| !timeout.is_zero()) {
+        builder = builder.read_timeout(timeout);
+    }
+
+    let no_proxy = config.get("proxy.no_proxy").and_then(|list| NoProxy::from_string(&list));
+    if let Some(url) = config.get("proxy.https") {
+        let proxy = Proxy::https(&url).map_err(|e| invalid("proxy.https", &url, e))?;
+        builder = builder.proxy(proxy.no_proxy(no_proxy.clone()));
+    }
+    if let Some(url) = config.get("proxy.http") {
+        let proxy = Proxy::http(&url).map_err(|e| invalid("proxy.http", &url, e))?;
+        builder = builder.proxy(proxy.no_proxy(no_proxy));
+    }
+
+    if let Some(path) = config.get("tls.ca_bundle") {
+        let certificates =
+            Certificate::from_pem_bundle(&read(&path)?).map_err(|e| invalid("tls.ca_bundle", &path, e))?;
+        if certificates.is_empty() {
+            return Err(Error::Parse(format!("tls.ca_bundle: no certificates found in {}", path)));
+        }
+        for certificate in certificates {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+
+    if let Some(path) = config.get("tls.client_cert") {
+        let certificate = read(&path)?;
+        let key = match config.get("tls.client_key") {
+            Some(key_path) => read(&key_path)?,
+            None => certificate.clone(),
+        };
+        let identity =
+            Identity::from_pkcs8_pem(&certificate, &key).map_err(|e| invalid("tls.client_cert", &path, e))?;
+        builder = builder.identity(identity);
+    }
+
+    builder
+        .build()
+        .map_err(|e| Error::Network(format!("Could not set up the HTTP client: {}", e)))
+}
+
+fn read(path: &str) -> Result<Vec<u8>> {
+    fs::read(path).map_err(|e| Error::Other(format!("Could not read {}: {}", path, e)))
+}
+
+fn invalid(key: &str, value: &str, error: reqwest::Error) -> Error {
+    Error::Parse(format!("{}: '{}' is not usable: {}", key, value, error))
+}
@@ -0,0 +1,272 @@
+//! Version directories under the install root and system-wide installs.
+
+use std::cmp::Ordering;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use crate::error::{Error, Result};
+use crate::output::say;
+use crate::utils::command::{is_dry_run, plan, run};
+use crate::utils::extract::extract;
+use crate::utils::paths::{cache_dir, language_dir, version_dir};
+use crate::utils::shims::{executable_name, print_path_hint, shim_path, write_shims};
+use crate::utils::uninstall::Step;
+use crate::utils::version::{Scheme, Version};
+
+const DEFAULT_FILE: &str = "default";
+
+#[derive(Debug, Clone, Copy)]
+pub struct Layout {
+    pub language: &'static str,
+    pub scheme: Scheme,
+    pub bin_subdir: &'static str,
+    pub binaries: &'static [&'static str],
+    pub version_args: &'static [&'static str],
+}
+
+pub fn install_into_root(layout: &Layout, version: &str, archive: &Path) -> Result<PathBuf> {
+    let dir = language_dir(layout.language);
+    let dest = version_dir(layout.language, version);
+    if is_dry_run() {
+        plan(format!("would extract {} into {}", archive.display(), dest.display()));
+        if dest.exists() {
+            plan(format!("would replace the existing {}", dest.display()));
+        }
+        if default_version(layout.language).is_none() {
+            set_default_version(layout, version)?;
+        }
+        return Ok(dest);
+    }
+    let staging = dir.join(format!(".staging-{}", version));
+    let backup = dir.join(format!(".backup-{}", version));
+    for leftover in [&staging, &backup] {
+        if leftover.exists() {
+            fs::remove_dir_all(leftover)?;
+        }
+    }
+
+    let staged = extract(archive, &staging, 1).and_then(|_| validate_install(layout, &staging, version));
+    if let Err(e) = staged {
+        fs::remove_dir_all(&staging)?;
+        return Err(e);
+    }
+
+    if dest.exists() {
+        fs::rename(&dest, &backup)?;
+    }
+    if let Err(e) = fs::rename(&staging, &dest) {
+        if backup.exists() {
+            fs::rename(&backup, &dest)?;
+            say!("Restored the previous {} {}", layout.language, version);
+        }
+        fs::remove_dir_all(&staging)?;
+        return Err(e.into());
+    }
+    if backup.exists() {
+        fs::remove_dir_all(&backup)?;
+    }
+
+    say!("Installed into {}", dest.display());
+    match default_version(layout.language) {
+        Some(current) if current != version => {
+            say!(
+                "{} remains the default; run 'language-revamp {} use {}' to switch.",
+                current, layout.language, version
+            );
+        }
+        _ => set_default_version(layout, version)?,
+    }
+    Ok(dest)
+}
+
+pub fn install_system_wide(layout: &Layout, version: &str, archive: &Path, target: &Path) -> Result<()> {
+    let name = target
+        .file_name()
+        .ok_or_else(|| Error::Other(format!("Invalid install target: {}", target.display())))?;
+    let staging = cache_dir().join("staging").join(name);
+    if is_dry_run() {
+        plan(format!("would extract {} and move it into {} with sudo", archive.display(), target.display()));
+        if target.exists() {
+            plan(format!("would replace the existing {}", target.display()));
+        }
+        return Ok(());
+    }
+    if staging.exists() {
+        fs::remove_dir_all(&staging)?;
+    }
+
+    let staged = extract(archive, &staging, 1).and_then(|_| validate_install(layout, &staging, version));
+    if let Err(e) = staged {
+        fs::remove_dir_all(&staging)?;
+        return Err(e);
+    }
+
+    let mut backup = target.as_os_str().to_owned();
+    backup.push(".backup");
+    let backup = PathBuf::from(backup);
+    let parent = target.parent().unwrap_or(Path::new("/"));
+
+    run_sudo(&[OsStr::new("mkdir"), OsStr::new("-p"), parent.as_os_str()])?;
+    run_sudo(&[OsStr::new("rm"), OsStr::new("-rf"), backup.as_os_str()])?;
+    let had_previous = target.exists();
+    if had_previous {
+        run_sudo(&[OsStr::new("mv"), target.as_os_str(), backup.as_os_str()])?;
+    }
+
+    let swapped = run_sudo(&[OsStr::new("mv"), staging.as_os_str(), target.as_os_str()]);
+    if staging.exists() {
+        fs::remove_dir_all(&staging)?;
+    }
+    if let Err(e) = swapped {
+        if had_previous {
+            run_sudo(&[OsStr::new("rm"), OsStr::new("-rf"), target.as_os_str()])?;
+            run_sudo(&[OsStr::new("mv"), backup.as_os_str(), target.as_os_str()])?;
+            say!("Restored the previous installation at {}", target.display());
+        }
+        return Err(e);
+    }
+    if had_previous {
+        run_sudo(&[OsStr::new("rm"), OsStr::new("-rf"), backup.as_os_str()])?;
+    }
+
+    say!("Installed into {}", target.display());
+    Ok(())
+}
+
+pub fn installed_versions(layout: &Layout) -> Result<Vec<String>> {
+    let dir = language_dir(layout.language);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut versions = Vec::new();
+    for entry in fs::read_dir(&dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if entry.file_type()?.is_dir() && !name.starts_with('.') {
+            versions.push(name);
+        }
+    }
+
+    versions.sort_by(|a, b| compare_version_strings(layout.scheme, a, b));
+    Ok(versions)
+}
+
+pub fn resolve_installed_version(layout: &Layout, requested: &str) -> Result<String> {
+    installed_versions(layout)?
+        .into_iter()
+        .rev()
+        .find(|version| matches_version_prefix(version, requested))
+        .ok_or_else(|| Error::NotInstalled(format!("{} {}", layout.language, requested)))
+}
+
+pub fn matches_version_prefix(version: &str, requested: &str) -> bool {
+    version == requested
+        || version
+            .strip_prefix(requested)
+            .is_some_and(|rest| rest.starts_with(['.', '+', '-']))
+}
+
+pub fn default_version(language: &str) -> Option<String> {
+    fs::read_to_string(language_dir(language).join(DEFAULT_FILE))
+        .ok()
+        .map(|version| version.trim().to_string())
+        .filter(|version| !version.is_empty() && version_dir(language, version).is_dir())
+}
+
+pub fn set_default_version(layout: &Layout, version: &str) -> Result<()> {
+    let dir = version_dir(layout.language, version);
+    if is_dry_run() {
+        plan(format!("would make {} {} the default", layout.language, version));
+        return write_shims(layout.binaries);
+    }
+    if !dir.is_dir() {
+        return Err(Error::NotInstalled(format!("{} {}", layout.language, version)));
+    }
+
+    fs::write(language_dir(layout.language).join(DEFAULT_FILE), format!("{}\n", version))?;
+    write_shims(layout.binaries)?;
+    say!("Now using {} {} by default.", layout.language, version);
+    print_path_hint();
+    Ok(())
+}
+
+pub fn managed_uninstall_steps(layout: &Layout, version: Option<&str>) -> Result<Vec<Step>> {
+    let dir = language_dir(layout.language);
+    let installed = installed_versions(layout)?;
+    if installed.is_empty() {
+        return Err(Error::NotInstalled(layout.language.to_string()));
+    }
+
+    if let Some(version) = version {
+        let resolved = resolve_installed_version(layout, version)?;
+        if installed.len() > 1 {
+            let mut steps = vec![Step::remove(version_dir(layout.language, &resolved))];
+            let digest = dir.join(format!("{}.sha256", resolved));
+            if digest.exists() {
+                steps.push(Step::remove(digest));
+            }
+            return Ok(steps);
+        }
+    }
+
+    let mut steps = vec![Step::remove(&dir)];
+    steps.extend(
+        layout
+            .binaries
+            .iter()
+            .map(|binary| shim_path(binary))
+            .filter(|shim| shim.exists())
+            .map(Step::remove),
+    );
+    Ok(steps)
+}
+
+fn validate_install(layout: &Layout, dir: &Path, version: &str) -> Result<()> {
+    let Some(binary) = layout.binaries.first() else {
+        return Ok(());
+    };
+    let program = dir.join(layout.bin_subdir).join(executable_name(binary));
+    let output = Command::new(&program)
+        .args(layout.version_args)
+        .output()
+        .map_err(|e| Error::command_failed(program.display().to_string(), e.to_string()))?;
+
+    let reported = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let matches = reported
+        .match_indices(version)
+        .any(|(index, _)| !reported[index + version.len()..].starts_with(|c: char| c.is_ascii_digit()));
+    if !output.status.success() || !matches {
+        return Err(Error::command_failed(
+            format!("{} {}", binary, layout.version_args.join(" ")),
+            format!("expected version {}, got '{}'", version, reported.trim()),
+        ));
+    }
+
+    say!("Validated {} {}", binary, version);
+    Ok(())
+}
+
+fn run_sudo(args: &[&OsStr]) -> Result<()> {
+    let status = run(Command::new("sudo").args(args))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Error::command_failed(
+            format!("sudo {}", args.iter().map(|arg| arg.to_string_lossy()).collect::<Vec<_>>().join(" ")),
+            format!("exited with {}", status),
+        ))
+    }
+}
+
+fn compare_version_strings(scheme: Scheme, a: &str, b: &str) -> Ordering {
+    match (Version::parse(scheme, a), Version::parse(scheme, b)) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        _ => a.cmp(b),
+    }
+}
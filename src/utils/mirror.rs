@@ -0,0 +1,19 @@
+//! Metadata and download URLs, following any configured mirrors.
+
+use crate::settings;
+
+pub fn metadata_url(language: &str, path: &str) -> String {
+    endpoint(language, "metadata_url", path)
+}
+
+pub fn download_url(language: &str, path: &str) -> String {
+    endpoint(language, "download_url", path)
+}
+
+fn endpoint(language: &str, kind: &str, path: &str) -> String {
+    let base = settings::get()
+        .config
+        .get(&format!("{}.{}", language, kind))
+        .unwrap_or_default();
+    format!("{}/{}", base.trim_end_matches('/'), path.trim_start_matches('/'))
+}
@@ -1,4 +1,19 @@
-﻿pub mod system;
-pub mod version;
-
+//! Building blocks shared by the language managers.
 
+pub mod cache;
+pub mod checksum;
+pub mod command;
+pub mod download;
+pub mod extract;
+pub mod fetcher;
+pub mod http;
+pub mod install;
+pub mod mirror;
+pub mod paths;
+pub mod provider;
+pub mod resolve;
+pub mod runner;
+pub mod shims;
+pub mod system;
+pub mod uninstall;
+pub mod version;
@@ -0,0 +1,64 @@
+//! Locations of the install root, caches, shims and configuration file.
+
+use std::env;
+use std::path::PathBuf;
+use crate::settings;
+
+pub fn default_install_root() -> PathBuf {
+    dirs::data_local_dir()
+        .or_else(|| dirs::home_dir().map(|home| home.join(".local").join("share")))
+        .unwrap_or_else(env::temp_dir)
+        .join("language-revamp")
+}
+
+pub fn install_root() -> PathBuf {
+    settings::get().install_root.clone()
+}
+
+pub fn language_dir(language: &str) -> PathBuf {
+    install_root().join(language)
+}
+
+pub fn version_dir(language: &str, version: &str) -> PathBuf {
+    language_dir(language).join(version)
+}
+
+pub fn shims_dir() -> PathBuf {
+    install_root().join("shims")
+}
+
+pub fn cache_dir() -> PathBuf {
+    if let Some(cache) = env::var_os("LANGUAGE_REVAMP_CACHE").filter(|cache| !cache.is_empty()) {
+        return PathBuf::from(cache);
+    }
+
+    dirs::cache_dir()
+        .unwrap_or_else(env::temp_dir)
+        .join("language-revamp")
+}
+
+pub fn downloads_dir() -> PathBuf {
+    cache_dir().join("downloads")
+}
+
+pub fn metadata_cache_dir() -> PathBuf {
+    cache_dir().join("metadata")
+}
+
+pub fn config_file() -> PathBuf {
+    if let Some(path) = env::var_os("LANGUAGE_REVAMP_CONFIG").filter(|path| !path.is_empty()) {
+        return PathBuf::from(path);
+    }
+
+    let base = if cfg!(windows) {
+        dirs::config_dir()
+    } else {
+        env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|home| home.join(".config")))
+    };
+    base.unwrap_or_else(env::temp_dir)
+        .join("language-revamp")
+        .join("config.toml")
+}
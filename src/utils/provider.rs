@@ -0,0 +1,131 @@
+//! Working out which tool or package manager installed a binary.
+
+use std::fmt;
+use std::path::Path;
+use serde::Serialize;
+use crate::utils::paths::install_root;
+use crate::utils::runner::CommandRunner;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Provider {
+    LanguageRevamp,
+    Apt,
+    Brew,
+    Conda,
+    Nvm,
+    Pyenv,
+    Rustup,
+    Snap,
+    Winget,
+    System,
+    Manual,
+    Unknown,
+}
+
+impl fmt::Display for Provider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Provider::LanguageRevamp => "language-revamp",
+            Provider::Apt => "apt",
+            Provider::Brew => "brew",
+            Provider::Conda => "conda",
+            Provider::Nvm => "nvm",
+            Provider::Pyenv => "pyenv",
+            Provider::Rustup => "rustup",
+            Provider::Snap => "snap",
+            Provider::Winget => "winget",
+            Provider::System => "system",
+            Provider::Manual => "manual",
+            Provider::Unknown => "unknown",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+pub fn detect_provider(runner: &dyn CommandRunner, path: &Path) -> Provider {
+    if path.starts_with(install_root()) {
+        return Provider::LanguageRevamp;
+    }
+    if snap_name(path).is_some() {
+        return Provider::Snap;
+    }
+
+    let resolved = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let text = resolved.to_string_lossy().replace('\\', "/").to_ascii_lowercase();
+    if resolved.starts_with(install_root()) {
+        Provider::LanguageRevamp
+    } else if text.contains("/.nvm/") || text.contains("/nvm/") {
+        Provider::Nvm
+    } else if text.contains("/.pyenv/") {
+        Provider::Pyenv
+    } else if text.contains("conda") {
+        Provider::Conda
+    } else if text.contains("/.rustup/") || text.contains("/.cargo/bin/") {
+        Provider::Rustup
+    } else if text.contains("/cellar/") || text.starts_with("/opt/homebrew/") || text.contains("/linuxbrew/") {
+        Provider::Brew
+    } else if text.contains("/winget/") || text.contains("/windowsapps/") {
+        Provider::Winget
+    } else if text.starts_with("/usr/bin/") || text.starts_with("/bin/") || text.starts_with("/usr/lib/") {
+        match dpkg_owner(runner, &resolved) {
+            Some(_) => Provider::Apt,
+            None => Provider::System,
+        }
+    } else if text.starts_with("/usr/local/") || text.starts_with("/opt/") || text.contains("/program files/") {
+        Provider::Manual
+    } else {
+        Provider::Unknown
+    }
+}
+
+pub fn dpkg_owner(runner: &dyn CommandRunner, path: &Path) -> Option<String> {
+    let output = runner.output("dpkg", &["-S", path.to_str()?]).ok()?;
+    if !output.success {
+        return None;
+    }
+    output
+        .stdout
+        .lines()
+        .next()?
+        .split(':')
+        .next()
+        .map(|package| package.trim().to_string())
+        .filter(|package| !package.is_empty())
+}
+
+pub fn snap_name(path: &Path) -> Option<String> {
+    let mut components = path.strip_prefix("/snap").ok()?.iter();
+    let name = match components.next()?.to_str()? {
+        "bin" => components.next()?.to_str()?.split('.').next()?,
+        name => name,
+    };
+    Some(name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::runner::ScriptedRunner;
+
+    #[test]
+    fn system_binaries_are_apt_only_when_dpkg_owns_them() {
+        let path = Path::new("/usr/bin/language-revamp-test-tool");
+        let debian = ScriptedRunner::new().stdout(
+            "dpkg -S /usr/bin/language-revamp-test-tool",
+            "tool: /usr/bin/language-revamp-test-tool",
+        );
+        assert_eq!(detect_provider(&debian, path), Provider::Apt);
+        assert_eq!(detect_provider(&ScriptedRunner::new(), path), Provider::System);
+    }
+
+    #[test]
+    fn dpkg_names_the_owning_package() {
+        let runner = ScriptedRunner::new()
+            .stdout("dpkg -S /usr/bin/python3.12", "python3.12-minimal: /usr/bin/python3.12\n")
+            .failing("dpkg -S /usr/bin/go", "dpkg-query: no path found matching pattern /usr/bin/go");
+        assert_eq!(dpkg_owner(&runner, Path::new("/usr/bin/python3.12")).as_deref(), Some("python3.12-minimal"));
+        assert_eq!(dpkg_owner(&runner, Path::new("/usr/bin/go")), None);
+        assert_eq!(dpkg_owner(&ScriptedRunner::new(), Path::new("/usr/bin/node")), None);
+    }
+}
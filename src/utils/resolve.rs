@@ -0,0 +1,117 @@
+//! Versions requested by environment variables and project version files.
+
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::error::{Error, Result};
+use crate::manager::LanguageManager;
+
+const TOOL_VERSIONS_FILE: &str = ".tool-versions";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionSource {
+    Environment(String),
+    ProjectFile(PathBuf),
+    GlobalDefault,
+}
+
+impl fmt::Display for VersionSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VersionSource::Environment(variable) => write!(f, "${}", variable),
+            VersionSource::ProjectFile(path) => write!(f, "{}", path.display()),
+            VersionSource::GlobalDefault => write!(f, "global default"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ActiveVersion {
+    pub version: String,
+    pub source: VersionSource,
+}
+
+pub fn resolve_active_version(manager: &dyn LanguageManager) -> Result<ActiveVersion> {
+    if let Some(requested) = requested_version(manager) {
+        return Ok(requested);
+    }
+
+    manager
+        .global_version()
+        .map(|version| ActiveVersion {
+            version,
+            source: VersionSource::GlobalDefault,
+        })
+        .ok_or_else(|| Error::NotInstalled(format!("{} (no default version set)", manager.display_name())))
+}
+
+pub fn requested_version<M: LanguageManager + ?Sized>(manager: &M) -> Option<ActiveVersion> {
+    let variable = version_variable(manager.name());
+    if let Ok(version) = env::var(&variable)
+        && !version.trim().is_empty()
+    {
+        return Some(ActiveVersion {
+            version: manager.normalize_version(version.trim()),
+            source: VersionSource::Environment(variable),
+        });
+    }
+
+    let cwd = env::current_dir().ok()?;
+    find_project_version(manager, &cwd)
+}
+
+pub fn find_project_version<M: LanguageManager + ?Sized>(manager: &M, start: &Path) -> Option<ActiveVersion> {
+    for dir in start.ancestors() {
+        let found = manager
+            .version_files()
+            .iter()
+            .map(|file_name| dir.join(file_name))
+            .find_map(|path| read_version_file(&path).map(|version| (version, path)))
+            .or_else(|| {
+                let path = dir.join(TOOL_VERSIONS_FILE);
+                read_tool_versions(&path, manager.tool_versions_name()).map(|version| (version, path))
+            });
+
+        if let Some((version, path)) = found {
+            return Some(ActiveVersion {
+                version: manager.normalize_version(&version),
+                source: VersionSource::ProjectFile(path),
+            });
+        }
+    }
+    None
+}
+
+pub fn version_variable(language: &str) -> String {
+    format!("LANGUAGE_REVAMP_{}_VERSION", language.to_ascii_uppercase())
+}
+
+fn read_version_file(path: &Path) -> Option<String> {
+    let contents = fs::read_to_string(path).ok()?;
+    if let Ok(document) = contents.parse::<toml::Table>() {
+        return document
+            .get("toolchain")
+            .and_then(|toolchain| toolchain.get("channel"))
+            .and_then(|channel| channel.as_str())
+            .map(str::to_string);
+    }
+
+    contents
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+fn read_tool_versions(path: &Path, tool: &str) -> Option<String> {
+    let contents = fs::read_to_string(path).ok()?;
+    contents.lines().find_map(|line| {
+        let mut fields = line.split('#').next().unwrap_or("").split_whitespace();
+        if fields.next() == Some(tool) {
+            fields.next().map(str::to_string)
+        } else {
+            None
+        }
+    })
+}
@@ -0,0 +1,106 @@
+//! The [`CommandRunner`] trait used to query installed toolchains.
+
+use std::io;
+use std::path::PathBuf;
+use std::process::Command;
+use which::which;
+
+#[derive(Debug, Clone, Default)]
+pub struct CommandOutput {
+    pub success: bool,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl CommandOutput {
+    pub fn text(&self) -> &str {
+        match self.stdout.trim() {
+            "" => self.stderr.trim(),
+            stdout => stdout,
+        }
+    }
+}
+
+pub trait CommandRunner: Send + Sync {
+    fn output(&self, program: &str, args: &[&str]) -> io::Result<CommandOutput>;
+
+    fn locate(&self, program: &str) -> Option<PathBuf>;
+}
+
+pub struct SystemRunner;
+
+impl CommandRunner for SystemRunner {
+    fn output(&self, program: &str, args: &[&str]) -> io::Result<CommandOutput> {
+        let output = Command::new(program).args(args).output()?;
+        Ok(CommandOutput {
+            success: output.status.success(),
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        })
+    }
+
+    fn locate(&self, program: &str) -> Option<PathBuf> {
+        which(program).ok()
+    }
+}
+
+#[cfg(test)]
+pub use scripted::ScriptedRunner;
+
+#[cfg(test)]
+mod scripted {
+    use std::collections::HashMap;
+    use std::io;
+    use std::path::PathBuf;
+    use super::{CommandOutput, CommandRunner};
+
+    #[derive(Default)]
+    pub struct ScriptedRunner {
+        outputs: HashMap<String, CommandOutput>,
+    }
+
+    impl ScriptedRunner {
+        pub fn new() -> ScriptedRunner {
+            ScriptedRunner::default()
+        }
+
+        pub fn stdout(self, command: &str, stdout: &str) -> ScriptedRunner {
+            self.script(command, true, stdout, "")
+        }
+
+        pub fn stderr(self, command: &str, stderr: &str) -> ScriptedRunner {
+            self.script(command, true, "", stderr)
+        }
+
+        pub fn failing(self, command: &str, stderr: &str) -> ScriptedRunner {
+            self.script(command, false, "", stderr)
+        }
+
+        fn script(mut self, command: &str, success: bool, stdout: &str, stderr: &str) -> ScriptedRunner {
+            let output = CommandOutput {
+                success,
+                stdout: stdout.to_string(),
+                stderr: stderr.to_string(),
+            };
+            self.outputs.insert(command.to_string(), output);
+            self
+        }
+    }
+
+    impl CommandRunner for ScriptedRunner {
+        fn output(&self, program: &str, args: &[&str]) -> io::Result<CommandOutput> {
+            let command = std::iter::once(program).chain(args.iter().copied()).collect::<Vec<_>>().join(" ");
+            self.outputs
+                .get(&command)
+                .cloned()
+                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("{} is not scripted", command)))
+        }
+
+        fn locate(&self, program: &str) -> Option<PathBuf> {
+            self.outputs
+                .keys()
+                .any(|command| command.split(' ').next() == Some(program))
+                .then(|| PathBuf::from("/usr/bin").join(program))
+        }
+    }
+}
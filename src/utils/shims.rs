@@ -0,0 +1,127 @@
+//! Shims that dispatch to the default version of each tool.
+
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use crate::error::{Error, Result};
+use crate::manager::registry;
+use crate::output::say;
+use crate::utils::command::{is_dry_run, plan};
+use crate::utils::paths::{install_root, shims_dir};
+use crate::utils::resolve::resolve_active_version;
+
+pub fn write_shims(binaries: &[&str]) -> Result<()> {
+    let dir = shims_dir();
+    if is_dry_run() {
+        plan(format!("would write shims for {} to {}", binaries.join(", "), dir.display()));
+        return Ok(());
+    }
+    fs::create_dir_all(&dir)?;
+    let exe = env::current_exe()?;
+
+    for binary in binaries {
+        write_shim(&dir, &exe, binary)?;
+    }
+    Ok(())
+}
+
+pub fn print_path_hint() {
+    let dir = shims_dir();
+    let on_path = env::var_os("PATH")
+        .map(|path| env::split_paths(&path).any(|entry| entry == dir))
+        .unwrap_or(false);
+    if !on_path {
+        say!("Add {} to your PATH to use the managed toolchains.", dir.display());
+    }
+}
+
+pub fn shim_path(binary: &str) -> PathBuf {
+    shims_dir().join(shim_name(binary))
+}
+
+pub fn run_shim(binary: &str, args: &[OsString]) -> Result<()> {
+    let manager = registry()
+        .into_iter()
+        .find(|manager| manager.shim_binaries().contains(&binary))
+        .ok_or_else(|| Error::Usage(format!("'{}' is not a managed executable", binary)))?;
+
+    let active = resolve_active_version(manager.as_ref())?;
+    let not_installed = || {
+        Error::NotInstalled(format!(
+            "{} {} (requested by {})",
+            manager.display_name(),
+            active.version,
+            active.source
+        ))
+    };
+    let program = manager
+        .version_bin_dir(&active.version)
+        .map_err(|_| not_installed())?
+        .join(executable_name(binary));
+    if !program.exists() {
+        return Err(not_installed());
+    }
+
+    exec(Command::new(program).args(args))
+}
+
+pub fn executable_name(binary: &str) -> String {
+    if cfg!(windows) {
+        format!("{}.exe", binary)
+    } else {
+        binary.to_string()
+    }
+}
+
+#[cfg(unix)]
+fn shim_name(binary: &str) -> String {
+    binary.to_string()
+}
+
+#[cfg(not(unix))]
+fn shim_name(binary: &str) -> String {
+    format!("{}.cmd", binary)
+}
+
+#[cfg(unix)]
+fn write_shim(dir: &Path, exe: &Path, binary: &str) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let shim = dir.join(shim_name(binary));
+    let script = format!(
+        "#!/bin/sh\nexec \"{}\" --root \"{}\" exec {} \"$@\"\n",
+        exe.display(),
+        install_root().display(),
+        binary
+    );
+    fs::write(&shim, script)?;
+    fs::set_permissions(&shim, fs::Permissions::from_mode(0o755))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn write_shim(dir: &Path, exe: &Path, binary: &str) -> Result<()> {
+    let script = format!(
+        "@echo off\r\n\"{}\" --root \"{}\" exec {} %*\r\n",
+        exe.display(),
+        install_root().display(),
+        binary
+    );
+    fs::write(dir.join(shim_name(binary)), script)?;
+    Ok(())
+}
+
+#[cfg(unix)]
+fn exec(command: &mut Command) -> Result<()> {
+    use std::os::unix::process::CommandExt;
+
+    Err(command.exec().into())
+}
+
+#[cfg(not(unix))]
+fn exec(command: &mut Command) -> Result<()> {
+    let status = command.status()?;
+    std::process::exit(status.code().unwrap_or(1));
+}
@@ -1,4 +1,7 @@
-﻿#[derive(Debug, PartialEq)]
+//! Operating system detection.
+
+#[derive(Debug, PartialEq)]
+#[allow(clippy::enum_variant_names)]
 pub enum OS {
     Windows,
     Linux,
@@ -0,0 +1,336 @@
+//! Planning and carrying out removals.
+
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use crate::error::{Error, Result};
+use crate::manager::Installation;
+use crate::output::say;
+use crate::utils::command::{describe, is_dry_run, remove_path, run};
+use crate::utils::paths::install_root;
+use crate::utils::provider::{detect_provider, dpkg_owner, snap_name, Provider};
+use crate::utils::runner::CommandRunner;
+use crate::utils::shims::executable_name;
+
+#[derive(Debug, Clone)]
+pub enum Step {
+    Remove { path: PathBuf, elevated: bool },
+    Command { package: String, command: Vec<String>, frees: Vec<PathBuf> },
+}
+
+impl Step {
+    pub fn remove(path: impl Into<PathBuf>) -> Step {
+        let path = path.into();
+        let elevated = needs_elevation(&path);
+        Step::Remove { path, elevated }
+    }
+
+    pub fn command(package: impl Into<String>, command: &[&str]) -> Step {
+        Step::Command {
+            package: package.into(),
+            command: command.iter().map(|part| part.to_string()).collect(),
+            frees: Vec::new(),
+        }
+    }
+
+    pub fn freeing(mut self, path: impl Into<PathBuf>) -> Step {
+        if let Step::Command { frees, .. } = &mut self {
+            frees.push(path.into());
+        }
+        self
+    }
+
+    fn footprint(&self) -> u64 {
+        match self {
+            Step::Remove { path, .. } => disk_usage(path),
+            Step::Command { frees, .. } => frees.iter().map(|path| disk_usage(path)).sum(),
+        }
+    }
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Step::Remove { path, elevated } => {
+                write!(f, "{} ({})", path.display(), format_size(self.footprint()))?;
+                if *elevated {
+                    write!(f, " [sudo]")?;
+                }
+                Ok(())
+            }
+            Step::Command { package, command, frees } => {
+                write!(f, "package {} via `{}`", package, command.join(" "))?;
+                if !frees.is_empty() {
+                    write!(f, " ({})", format_size(self.footprint()))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+pub fn execute(name: &str, steps: &[Step], approve: impl FnOnce(&[Step]) -> Result<bool>) -> Result<()> {
+    if steps.is_empty() {
+        return Err(Error::NotInstalled(name.to_string()));
+    }
+
+    say!("The following will be removed:");
+    for step in steps {
+        say!("  {}", step);
+    }
+    if !is_dry_run() && !approve(steps)? {
+        return Err(Error::Other("Uninstall cancelled".to_string()));
+    }
+
+    let mut freed = 0;
+    let mut removed = Vec::new();
+    let mut failure = None;
+    for step in steps {
+        let before = step.footprint();
+        match run_step(step) {
+            Ok(()) => {
+                freed += before.saturating_sub(step.footprint());
+                removed.push(step);
+            }
+            Err(e) => {
+                failure = Some(e);
+                break;
+            }
+        }
+    }
+
+    if !is_dry_run() {
+        say!("\nSummary:");
+        for step in &removed {
+            match step {
+                Step::Remove { path, .. } => say!("  ✔ removed {}", path.display()),
+                Step::Command { package, .. } => say!("  ✔ uninstalled package {}", package),
+            }
+        }
+        say!("Freed {} on disk.", format_size(freed));
+    }
+
+    match failure {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+pub struct Toolchain {
+    pub name: &'static str,
+    pub binaries: &'static [&'static str],
+    pub markers: &'static [&'static str],
+    pub uninstaller: Option<&'static str>,
+    pub conda_package: &'static str,
+    pub workspaces: fn(&dyn CommandRunner) -> Vec<PathBuf>,
+}
+
+pub fn toolchain_steps(
+    runner: &dyn CommandRunner,
+    toolchain: &Toolchain,
+    installation: &Installation,
+) -> Result<Vec<Step>> {
+    let provider = detect_provider(runner, &installation.path);
+    let resolved = installation
+        .path
+        .canonicalize()
+        .unwrap_or_else(|_| installation.path.clone());
+
+    match provider {
+        Provider::Apt => {
+            let package = dpkg_owner(runner, &resolved).ok_or_else(|| {
+                Error::Other(format!("{} is not owned by any installed package", resolved.display()))
+            })?;
+            Ok(vec![Step::command(&package, &["sudo", "apt", "remove", &package])])
+        }
+        Provider::Brew => {
+            let formula = resolved
+                .iter()
+                .skip_while(|component| *component != "Cellar")
+                .nth(1)
+                .map(|formula| formula.to_string_lossy().to_string())
+                .ok_or_else(|| {
+                    Error::Other(format!("Could not find the Homebrew formula for {}", resolved.display()))
+                })?;
+            Ok(vec![Step::command(&formula, &["brew", "uninstall", &formula])])
+        }
+        Provider::Snap => {
+            let snap = snap_name(&installation.path)
+                .or_else(|| snap_name(&resolved))
+                .ok_or_else(|| Error::Other(format!("Could not find the snap for {}", installation.path.display())))?;
+            Ok(vec![Step::command(&snap, &["sudo", "snap", "remove", &snap])])
+        }
+        Provider::Conda => {
+            let prefix = conda_prefix(&installation.path).ok_or_else(|| {
+                Error::Other(format!("Could not find the conda environment of {}", installation.path.display()))
+            })?;
+            let prefix = prefix.to_string_lossy();
+            Ok(vec![Step::command(
+                toolchain.conda_package,
+                &["conda", "remove", "-y", "-p", &prefix, toolchain.conda_package],
+            )])
+        }
+        Provider::Manual | Provider::Unknown => tarball_steps(runner, toolchain, installation, &resolved),
+        Provider::System => Err(Error::Other(format!(
+            "The {} at {} belongs to the operating system; remove it with the system package manager",
+            toolchain.name,
+            installation.path.display()
+        ))),
+        Provider::LanguageRevamp => Err(Error::Other(format!(
+            "The {} at {} is managed by language-revamp; uninstall it without --system",
+            toolchain.name,
+            installation.path.display()
+        ))),
+        _ => Err(Error::Other(format!(
+            "The {} at {} was installed by {}; remove it with {}",
+            toolchain.name,
+            installation.path.display(),
+            provider,
+            provider
+        ))),
+    }
+}
+
+fn tarball_steps(
+    runner: &dyn CommandRunner,
+    toolchain: &Toolchain,
+    installation: &Installation,
+    binary: &Path,
+) -> Result<Vec<Step>> {
+    let unproven = || {
+        Error::Other(format!(
+            "Could not prove that {} belongs to a {} installation; remove it manually",
+            binary.display(),
+            toolchain.name
+        ))
+    };
+    let root = binary
+        .ancestors()
+        .skip(1)
+        .take(4)
+        .find(|dir| !toolchain.markers.is_empty() && toolchain.markers.iter().all(|marker| dir.join(marker).exists()))
+        .ok_or_else(unproven)?;
+
+    if let Some(uninstaller) = toolchain.uninstaller.map(|script| root.join(script))
+        && uninstaller.is_file()
+    {
+        let script = uninstaller.to_string_lossy();
+        let command: Vec<&str> = if needs_elevation(&uninstaller) {
+            vec!["sudo", &script]
+        } else {
+            vec![&script]
+        };
+        return Ok(vec![Step::command(toolchain.name, &command)]);
+    }
+
+    let root = if root.ends_with("Contents/Home") {
+        root.parent().and_then(Path::parent).unwrap_or(root)
+    } else {
+        root
+    };
+    if let Some(path) = protected_paths().into_iter().find(|path| path.starts_with(root)) {
+        return Err(Error::Other(format!("Refusing to remove {}: it contains {}", root.display(), path.display())));
+    }
+    if let Some(workspace) = (toolchain.workspaces)(runner)
+        .into_iter()
+        .find(|workspace| workspace.starts_with(root) || root.starts_with(workspace))
+    {
+        return Err(Error::Other(format!(
+            "Refusing to remove {}: it overlaps the {} workspace {}",
+            root.display(),
+            toolchain.name,
+            workspace.display()
+        )));
+    }
+
+    let mut steps = vec![Step::remove(root)];
+    if let Some(dir) = installation.path.parent() {
+        for binary in toolchain.binaries {
+            let link = dir.join(executable_name(binary));
+            let is_link = fs::symlink_metadata(&link).is_ok_and(|metadata| metadata.file_type().is_symlink());
+            if is_link && link.canonicalize().is_ok_and(|target| target.starts_with(root)) {
+                steps.push(Step::remove(link));
+            }
+        }
+    }
+    Ok(steps)
+}
+
+fn protected_paths() -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = ["/usr/bin", "/usr/local/bin", "/usr/lib", "/opt/homebrew", "/etc"]
+        .iter()
+        .map(PathBuf::from)
+        .collect();
+    paths.extend(dirs::home_dir());
+    paths.push(install_root());
+    for variable in ["ProgramFiles", "ProgramFiles(x86)", "SystemRoot"] {
+        paths.extend(env::var_os(variable).map(PathBuf::from));
+    }
+    paths
+}
+
+fn run_step(step: &Step) -> Result<()> {
+    let mut command = match step {
+        Step::Remove { path, elevated: false } => return remove_path(path),
+        Step::Remove { path, elevated: true } => {
+            let mut command = Command::new("sudo");
+            command.arg("rm").arg("-rf").arg(path);
+            command
+        }
+        Step::Command { command, .. } => {
+            let mut process = Command::new(&command[0]);
+            process.args(&command[1..]);
+            process
+        }
+    };
+
+    let status = run(&mut command)?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Error::command_failed(describe(&command), format!("exited with {}", status)))
+    }
+}
+
+fn conda_prefix(binary: &Path) -> Option<PathBuf> {
+    binary
+        .ancestors()
+        .skip(1)
+        .find(|dir| dir.join("conda-meta").is_dir())
+        .map(Path::to_path_buf)
+}
+
+fn needs_elevation(path: &Path) -> bool {
+    cfg!(unix)
+        && !path.starts_with(install_root())
+        && !dirs::home_dir().is_some_and(|home| path.starts_with(home))
+}
+
+fn disk_usage(path: &Path) -> u64 {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|entry| disk_usage(&entry.path())).sum())
+        .unwrap_or(0)
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
@@ -1,17 +1,406 @@
-﻿use std::cmp::Ordering;
+//! Parsing and ordering version numbers in each language's scheme.
 
-pub fn compare_versions(installed: &str, latest: &str) -> Ordering {
-    let clean_installed = installed.trim_start_matches("go");
-    let clean_latest = latest.trim_start_matches("go");
+use std::cmp::Ordering;
+use std::fmt;
+use crate::error::{Error, Result};
 
-    let installed_parts: Vec<u32> = clean_installed
-        .split('.')
-        .filter_map(|p| p.parse().ok())
-        .collect();
-    let latest_parts: Vec<u32> = clean_latest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scheme {
+    Go,
+    Java,
+    Pep440,
+    Semver,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Identifier {
+    Numeric(u64),
+    Alpha(String),
+}
+
+impl fmt::Display for Identifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Identifier::Numeric(n) => write!(f, "{}", n),
+            Identifier::Alpha(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Version {
+    scheme: Scheme,
+    release: Vec<u64>,
+    pre: Vec<Identifier>,
+    post: Option<u64>,
+    dev: Option<u64>,
+    build: Option<u64>,
+}
+
+impl Version {
+    pub fn parse(scheme: Scheme, input: &str) -> Result<Version> {
+        let trimmed = input.trim();
+        let parsed = match scheme {
+            Scheme::Go => parse_go(trimmed),
+            Scheme::Java => parse_java(trimmed),
+            Scheme::Pep440 => parse_pep440(trimmed),
+            Scheme::Semver => parse_semver(trimmed),
+        };
+        parsed.ok_or_else(|| Error::Parse(format!("Invalid version '{}'", input)))
+    }
+
+    pub fn major(&self) -> u64 {
+        self.release.first().copied().unwrap_or(0)
+    }
+
+    pub fn release(&self) -> &[u64] {
+        &self.release
+    }
+
+    pub fn build(&self) -> Option<u64> {
+        self.build
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.release.len() >= 3 || !self.pre.is_empty()
+    }
+
+    fn new(scheme: Scheme, release: Vec<u64>) -> Version {
+        Version {
+            scheme,
+            release,
+            pre: Vec::new(),
+            post: None,
+            dev: None,
+            build: None,
+        }
+    }
+
+    fn pre_key(&self) -> PreKey<'_> {
+        if self.pre.is_empty() && self.post.is_none() && self.dev.is_some() {
+            PreKey::DevOnly
+        } else if self.pre.is_empty() {
+            PreKey::Final
+        } else {
+            PreKey::Pre(&self.pre)
+        }
+    }
+
+    fn dev_key(&self) -> (u8, u64) {
+        match self.dev {
+            Some(n) => (0, n),
+            None => (1, 0),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum PreKey<'a> {
+    DevOnly,
+    Pre(&'a [Identifier]),
+    Final,
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare_release(&self.release, &other.release)
+            .then_with(|| self.pre_key().cmp(&other.pre_key()))
+            .then_with(|| self.post.cmp(&other.post))
+            .then_with(|| self.dev_key().cmp(&other.dev_key()))
+            .then_with(|| self.build.cmp(&other.build))
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let release = self
+            .release
+            .iter()
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(".");
+        let pre = self
+            .pre
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<_>>();
+
+        match self.scheme {
+            Scheme::Go => write!(f, "go{}{}", release, pre.concat()),
+            Scheme::Pep440 => {
+                write!(f, "{}{}", release, pre.concat())?;
+                if let Some(post) = self.post {
+                    write!(f, ".post{}", post)?;
+                }
+                if let Some(dev) = self.dev {
+                    write!(f, ".dev{}", dev)?;
+                }
+                Ok(())
+            }
+            Scheme::Java if self.major() <= 8 => {
+                write!(f, "1.{}.{}", self.major(), self.release.get(1).unwrap_or(&0))?;
+                if let Some(update) = self.release.get(2).filter(|update| **update > 0) {
+                    write!(f, "_{}", update)?;
+                }
+                if let Some(build) = self.build {
+                    write!(f, "-b{:02}", build)?;
+                }
+                Ok(())
+            }
+            Scheme::Java | Scheme::Semver => {
+                write!(f, "{}", release)?;
+                if !pre.is_empty() {
+                    write!(f, "-{}", pre.join("."))?;
+                }
+                if let Some(build) = self.build {
+                    write!(f, "+{}", build)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn compare_release(a: &[u64], b: &[u64]) -> Ordering {
+    let len = a.len().max(b.len());
+    (0..len)
+        .map(|i| a.get(i).unwrap_or(&0).cmp(b.get(i).unwrap_or(&0)))
+        .find(|ordering| *ordering != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+fn split_release(input: &str) -> Option<(Vec<u64>, &str)> {
+    let mut release = Vec::new();
+    let mut rest = input;
+
+    loop {
+        let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        if digits == 0 {
+            return None;
+        }
+        release.push(rest[..digits].parse().ok()?);
+        rest = &rest[digits..];
+
+        match rest.strip_prefix('.') {
+            Some(next) if next.starts_with(|c: char| c.is_ascii_digit()) => rest = next,
+            _ => return Some((release, rest)),
+        }
+    }
+}
+
+fn split_number(input: &str) -> (Option<u64>, &str) {
+    let digits = input.len() - input.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    (input[..digits].parse().ok(), &input[digits..])
+}
+
+fn parse_identifiers(input: &str) -> Vec<Identifier> {
+    input
         .split('.')
-        .filter_map(|p| p.parse().ok())
-        .collect();
+        .filter(|id| !id.is_empty())
+        .map(|id| match id.parse() {
+            Ok(n) => Identifier::Numeric(n),
+            Err(_) => Identifier::Alpha(id.to_string()),
+        })
+        .collect()
+}
+
+fn parse_go(input: &str) -> Option<Version> {
+    let (release, rest) = split_release(input.strip_prefix("go").unwrap_or(input))?;
+    let mut version = Version::new(Scheme::Go, release);
+
+    if !rest.is_empty() {
+        let phase = rest.trim_end_matches(|c: char| c.is_ascii_digit());
+        let (number, _) = split_number(&rest[phase.len()..]);
+        if phase != "rc" && phase != "beta" {
+            return None;
+        }
+        version.pre = vec![Identifier::Alpha(phase.to_string()), Identifier::Numeric(number.unwrap_or(0))];
+    }
+
+    Some(version)
+}
+
+fn parse_semver(input: &str) -> Option<Version> {
+    let (release, rest) = split_release(input.strip_prefix('v').unwrap_or(input))?;
+    let mut version = Version::new(Scheme::Semver, release);
+
+    let rest = rest.split('+').next().unwrap_or("");
+    if let Some(pre) = rest.strip_prefix('-') {
+        version.pre = parse_identifiers(pre);
+    } else if !rest.is_empty() {
+        return None;
+    }
+
+    Some(version)
+}
+
+fn parse_java(input: &str) -> Option<Version> {
+    let (mut release, mut rest) = split_release(input)?;
+
+    if release.len() > 1 && release[0] == 1 {
+        release.remove(0);
+        if let Some(update) = rest.strip_prefix('_') {
+            let (number, remainder) = split_number(update);
+            release.truncate(2);
+            release.resize(2, 0);
+            release.push(number?);
+            rest = remainder;
+        }
+    }
+
+    let mut version = Version::new(Scheme::Java, release);
+
+    if let Some(pre) = rest.strip_prefix('-') {
+        let end = pre.find('+').unwrap_or(pre.len());
+        if pre.starts_with('b') && pre[1..end].chars().all(|c| c.is_ascii_digit()) {
+            version.build = pre[1..end].parse().ok();
+        } else {
+            version.pre = parse_identifiers(&pre[..end]);
+        }
+        rest = &pre[end..];
+    }
+
+    if let Some(build) = rest.strip_prefix('+') {
+        let (number, _) = split_number(build);
+        version.build = number;
+    }
+
+    Some(version)
+}
+
+fn parse_pep440(input: &str) -> Option<Version> {
+    let lowered = input.to_ascii_lowercase();
+    let normalized = lowered.strip_prefix('v').unwrap_or(&lowered);
+    let normalized = normalized.split('+').next().unwrap_or("");
+    let (release, mut rest) = split_release(normalized)?;
+    let mut version = Version::new(Scheme::Pep440, release);
+
+    let separated = rest.trim_start_matches(['.', '-', '_']);
+    for (spelling, phase) in [
+        ("alpha", "a"),
+        ("beta", "b"),
+        ("preview", "rc"),
+        ("pre", "rc"),
+        ("rc", "rc"),
+        ("a", "a"),
+        ("b", "b"),
+        ("c", "rc"),
+    ] {
+        if let Some(after) = separated.strip_prefix(spelling) {
+            let (number, remainder) = split_number(after.trim_start_matches(['.', '-', '_']));
+            version.pre = vec![Identifier::Alpha(phase.to_string()), Identifier::Numeric(number.unwrap_or(0))];
+            rest = remainder;
+            break;
+        }
+    }
+
+    rest = rest.trim_start_matches(['.', '_']);
+    if let Some(after) = rest.strip_prefix('-').filter(|s| s.starts_with(|c: char| c.is_ascii_digit())) {
+        let (number, remainder) = split_number(after);
+        version.post = number;
+        rest = remainder;
+    } else {
+        let rest_trimmed = rest.trim_start_matches('-');
+        for spelling in ["post", "rev", "r"] {
+            if let Some(after) = rest_trimmed.strip_prefix(spelling) {
+                let (number, remainder) = split_number(after.trim_start_matches(['.', '-', '_']));
+                version.post = Some(number.unwrap_or(0));
+                rest = remainder;
+                break;
+            }
+        }
+    }
+
+    rest = rest.trim_start_matches(['.', '-', '_']);
+    if let Some(after) = rest.strip_prefix("dev") {
+        let (number, remainder) = split_number(after);
+        version.dev = Some(number.unwrap_or(0));
+        rest = remainder;
+    }
+
+    if rest.is_empty() {
+        Some(version)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(scheme: Scheme, input: &str) -> Version {
+        Version::parse(scheme, input).unwrap()
+    }
+
+    fn assert_ascending(scheme: Scheme, versions: &[&str]) {
+        for pair in versions.windows(2) {
+            assert!(parse(scheme, pair[0]) < parse(scheme, pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn go_prereleases_sort_before_the_release() {
+        assert_ascending(Scheme::Go, &["go1.21beta1", "go1.21beta2", "go1.21rc1", "go1.21rc2", "go1.21.0", "go1.21.1", "go1.22rc1"]);
+        assert_eq!(parse(Scheme::Go, "go1.21"), parse(Scheme::Go, "1.21.0"));
+        assert_eq!(parse(Scheme::Go, "go1.22rc1").to_string(), "go1.22rc1");
+        assert!(Version::parse(Scheme::Go, "go1.22alpha1").is_err());
+    }
+
+    #[test]
+    fn java_8_keeps_its_legacy_spelling() {
+        let java_8 = parse(Scheme::Java, "1.8.0_432-b06");
+        assert_eq!(java_8.major(), 8);
+        assert_eq!(java_8.release(), [8, 0, 432]);
+        assert_eq!(java_8.build(), Some(6));
+        assert_eq!(java_8.to_string(), "1.8.0_432-b06");
+        assert_eq!(parse(Scheme::Java, "8.0.432").to_string(), "1.8.0_432");
+        assert_eq!(parse(Scheme::Java, "21.0.5+11").to_string(), "21.0.5+11");
+        assert_eq!(parse(Scheme::Java, "17.0.13+11-LTS").to_string(), "17.0.13+11");
+    }
+
+    #[test]
+    fn java_8_sorts_before_9_and_later() {
+        assert_ascending(
+            Scheme::Java,
+            &["1.8.0_392-b08", "1.8.0_432-b06", "9.0.4+11", "11.0.25+9", "17.0.13+11", "21.0.5+11", "21.0.5+12"],
+        );
+    }
+
+    #[test]
+    fn pep440_orders_dev_pre_final_and_post() {
+        assert_ascending(
+            Scheme::Pep440,
+            &["1.0.dev1", "1.0a1.dev1", "1.0a1", "1.0b2", "1.0rc1", "1.0", "1.0.post1.dev1", "1.0.post1", "1.0.post2", "1.1"],
+        );
+        assert_eq!(parse(Scheme::Pep440, "1.0alpha1"), parse(Scheme::Pep440, "1.0a1"));
+        assert_eq!(parse(Scheme::Pep440, "1.0-1"), parse(Scheme::Pep440, "1.0.post1"));
+        assert_eq!(parse(Scheme::Pep440, "1.0-1").to_string(), "1.0.post1");
+        assert_eq!(parse(Scheme::Pep440, "3.13.0rc2").to_string(), "3.13.0rc2");
+        assert!(Version::parse(Scheme::Pep440, "1.0-foo").is_err());
+    }
 
-    installed_parts.cmp(&latest_parts)
+    #[test]
+    fn semver_prereleases_sort_before_the_release() {
+        assert_ascending(
+            Scheme::Semver,
+            &["v20.0.0-rc.1", "v20.0.0-rc.2", "v20.0.0", "v20.10.0", "v22.12.0"],
+        );
+        assert_eq!(parse(Scheme::Semver, "v22.12.0"), parse(Scheme::Semver, "22.12.0+build.5"));
+        assert!(Version::parse(Scheme::Semver, "v22.x").is_err());
+    }
 }